@@ -0,0 +1,170 @@
+use std::f32::consts::PI;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use kolli_desu::epa::solve;
+use kolli_desu::gjk::{collides, collides_internal};
+use kolli_desu::shapes::{Aabb, Circle, ConvexPolygon, Shape};
+use kolli_desu::{Point, Vector};
+
+const VERTEX_COUNTS: [usize; 8] = [3, 4, 8, 16, 32, 64, 128, 256];
+
+/// Overlaps of the shapes in `penetration_depths`, from barely touching to nearly through each other.
+const DEPTHS: [f32; 6] = [0.001, 0.01, 0.1, 0.5, 1., 1.5];
+
+/// Position of the second shape where it overlaps the first one, and where it misses it.
+fn hit() -> Point<f32> {
+    Point::new(0.9, 0.4)
+}
+
+fn miss() -> Point<f32> {
+    Point::new(2.5, 1.2)
+}
+
+fn regular_polygon(count: usize, radius: f32) -> ConvexPolygon {
+    ConvexPolygon::new(
+        (0..count)
+            .map(|i| {
+                let angle = i as f32 * 2. * PI / count as f32;
+                Point::new(angle.cos(), angle.sin()) * radius
+            })
+            .collect(),
+    )
+}
+
+fn circle() -> Circle {
+    Circle::new(Point::new(0., 0.), 1.)
+}
+
+fn aabb() -> Aabb {
+    Aabb::new(Point::new(-1., -1.), Point::new(1., 1.))
+}
+
+fn polygon() -> ConvexPolygon {
+    regular_polygon(8, 1.)
+}
+
+fn pair<S1, S2>(c: &mut Criterion, name: &str, a: &S1, b: &S2)
+where
+    S1: Shape,
+    S2: Shape,
+{
+    let origin = Point::new(0., 0.);
+    let mut group = c.benchmark_group("collides");
+    for &(case, pos) in &[("hit", hit()), ("miss", miss())] {
+        group.bench_with_input(BenchmarkId::new(name, case), &pos, |bench, &pos| {
+            bench.iter(|| collides((a, black_box(origin)), (b, black_box(pos))))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("penetration");
+    group.bench_function(name, |bench| {
+        bench.iter(|| {
+            let (a, b) = ((a, black_box(origin)), (b, black_box(hit())));
+            let (_, simplex) = collides_internal(a, b);
+            solve(a, b, simplex)
+        })
+    });
+    group.finish();
+}
+
+fn shape_pairs(c: &mut Criterion) {
+    pair(c, "circle_circle", &circle(), &circle());
+    pair(c, "circle_aabb", &circle(), &aabb());
+    pair(c, "circle_polygon", &circle(), &polygon());
+    pair(c, "aabb_aabb", &aabb(), &aabb());
+    pair(c, "aabb_polygon", &aabb(), &polygon());
+    pair(c, "polygon_polygon", &polygon(), &polygon());
+}
+
+fn vertex_counts(c: &mut Criterion) {
+    let dirs: Vec<_> = (0..16)
+        .map(|i| {
+            let angle = i as f32 * 2. * PI / 16.;
+            Vector::new(angle.cos(), angle.sin())
+        })
+        .collect();
+    let mut group = c.benchmark_group("polygon_support");
+    for &count in &VERTEX_COUNTS {
+        let polygon = regular_polygon(count, 1.);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(count),
+            &polygon,
+            |bench, polygon| {
+                bench.iter(|| {
+                    for &dir in &dirs {
+                        black_box(polygon.farthest_in_dir(black_box(dir)));
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+
+    let origin = Point::new(0., 0.);
+    let mut group = c.benchmark_group("polygon_polygon");
+    for &count in &VERTEX_COUNTS {
+        let polygon = regular_polygon(count, 1.);
+        for &(case, pos) in &[("hit", hit()), ("miss", miss())] {
+            group.bench_with_input(BenchmarkId::new(case, count), &pos, |bench, &pos| {
+                bench.iter(|| collides((&polygon, black_box(origin)), (&polygon, black_box(pos))))
+            });
+        }
+        group.bench_with_input(
+            BenchmarkId::new("penetration", count),
+            &count,
+            |bench, _| {
+                bench.iter(|| {
+                    let (a, b) = ((&polygon, black_box(origin)), (&polygon, black_box(hit())));
+                    let (_, simplex) = collides_internal(a, b);
+                    solve(a, b, simplex)
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Distance along `dir` at which a copy of `shape` starts touching `shape`, found by bisection.
+fn touching_distance(shape: &dyn Shape, dir: Vector<f32>) -> f32 {
+    let origin = Point::new(0., 0.);
+    let (mut lo, mut hi) = (0., 4.);
+    for _ in 0..40 {
+        let mid = (lo + hi) / 2.;
+        if collides((shape, origin), (shape, Point::from(dir * mid))) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+fn penetration_depths(c: &mut Criterion) {
+    let origin = Point::new(0., 0.);
+    let dir = Vector::new(0.9, 0.4).normalize();
+    let mut group = c.benchmark_group("penetration_depth");
+    let shapes: [(&str, Box<dyn Shape>); 3] = [
+        ("circle_circle", Box::new(circle())),
+        ("aabb_aabb", Box::new(aabb())),
+        ("polygon_polygon", Box::new(polygon())),
+    ];
+    for (name, shape) in &shapes {
+        let touching = touching_distance(&**shape, dir);
+        for &depth in &DEPTHS {
+            let pos = Point::from(dir * (touching - depth));
+            group.bench_with_input(BenchmarkId::new(*name, depth), &pos, |bench, &pos| {
+                bench.iter(|| {
+                    let (a, b) = ((&**shape, black_box(origin)), (&**shape, black_box(pos)));
+                    let (_, simplex) = collides_internal(a, b);
+                    solve(a, b, simplex)
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, shape_pairs, vertex_counts, penetration_depths);
+criterion_main!(benches);
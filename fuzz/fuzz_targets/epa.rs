@@ -0,0 +1,36 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use kolli_desu::epa::solve;
+use kolli_desu::gjk::{collides, collides_internal};
+use kolli_desu_fuzz::{tolerance, Posed};
+
+fuzz_target!(|input: (Posed, Posed)| {
+    let (a, b) = input;
+    let (a_shape, b_shape) = (a.shape.build(), b.shape.build());
+    let (a_pose, b_pose) = ((&a_shape, a.position()), (&b_shape, b.position()));
+    let (collides_ab, simplex) = collides_internal(a_pose, b_pose);
+    if !collides_ab {
+        return;
+    }
+    let (normal, depth) = solve(a_pose, b_pose, simplex);
+    if !a.is_valid() || !b.is_valid() {
+        return;
+    }
+    let tolerance = tolerance(&a, &b);
+    assert!(
+        (normal.norm() - 1.).abs() < 1e-3,
+        "normal {} isn't unit length",
+        normal
+    );
+    assert!(depth >= -tolerance, "negative depth {}", depth);
+    // Moving b out along the normal by the depth has to separate the shapes.
+    let moved = b.position() + normal * (depth + tolerance);
+    assert!(
+        !collides(a_pose, (&b_shape, moved)),
+        "still colliding after moving by {} along {}",
+        depth,
+        normal
+    );
+});
@@ -0,0 +1,31 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use kolli_desu::epa::solve;
+use kolli_desu::gjk::collides_internal;
+use kolli_desu_fuzz::{tolerance, Posed};
+
+fuzz_target!(|input: (Posed, Posed)| {
+    let (a, b) = input;
+    let (a_shape, b_shape) = (a.shape.build(), b.shape.build());
+    let (a_pose, b_pose) = ((&a_shape, a.position()), (&b_shape, b.position()));
+    let (ab, ab_simplex) = collides_internal(a_pose, b_pose);
+    let (ba, ba_simplex) = collides_internal(b_pose, a_pose);
+    if !a.is_valid() || !b.is_valid() || ab == ba {
+        return;
+    }
+    // The order of the shapes can only change the answer when they barely touch.
+    let (_, depth) = if ab {
+        solve(a_pose, b_pose, ab_simplex)
+    } else {
+        solve(b_pose, a_pose, ba_simplex)
+    };
+    assert!(
+        depth <= tolerance(&a, &b),
+        "collides is {} one way and {} the other with depth {}",
+        ab,
+        ba,
+        depth
+    );
+});
@@ -0,0 +1,49 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+
+use kolli_desu::shapes::{ConvexPolygon, Shape as _, ShapeKind};
+use kolli_desu::{Point, Vector};
+use kolli_desu_fuzz::{Shape, LIMIT};
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    shape: Shape,
+    dir: (f32, f32),
+    /// Arbitrary corners, which don't have to be convex or even finite.
+    points: Vec<(f32, f32)>,
+}
+
+fuzz_target!(|input: Input| {
+    let dir = Vector::new(input.dir.0, input.dir.1);
+    if !input.points.is_empty() {
+        let points = input
+            .points
+            .iter()
+            .map(|&(x, y)| Point::new(x, y))
+            .collect();
+        ConvexPolygon::new(points).farthest_in_dir(dir);
+    }
+
+    let shape = input.shape.build();
+    let farthest = shape.farthest_in_dir(dir);
+    if !input.shape.is_valid() || !dir.iter().all(|c| c.abs() <= LIMIT) {
+        return;
+    }
+    // No corner may be farther than the one that was found.
+    if let ShapeKind::ConvexPolygon(polygon) = &shape {
+        let max = polygon
+            .points
+            .iter()
+            .map(|p| p.coords.dot(&dir))
+            .fold(f32::MIN, f32::max);
+        let tolerance = 1e-4 * (1. + max.abs());
+        assert!(
+            farthest.dot(&dir) >= max - tolerance,
+            "{} isn't the farthest point in {}",
+            farthest,
+            dir
+        );
+    }
+});
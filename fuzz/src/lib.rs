@@ -0,0 +1,147 @@
+//! Shapes built from arbitrary fuzzer input, shared by the fuzz targets.
+
+use arbitrary::Arbitrary;
+
+use kolli_desu::shapes::{Aabb, Circle, ConvexPolygon, ShapeKind};
+use kolli_desu::Point;
+
+/// A little less than a full turn, so the last corner can't wrap around past the first one.
+const FULL_TURN: f32 = ::std::f32::consts::TAU - 0.001;
+
+/// Dot products of larger coordinates can overflow, so invariants are only checked for shapes within this.
+pub const LIMIT: f32 = 1e6;
+
+#[derive(Arbitrary, Debug)]
+pub enum Shape {
+    Circle {
+        center: (f32, f32),
+        radius: f32,
+    },
+    Aabb {
+        from: (f32, f32),
+        to: (f32, f32),
+    },
+    /// Corners at angles around a circle, which is convex whenever the numbers are finite.
+    Polygon {
+        center: (f32, f32),
+        radius: f32,
+        angles: Vec<f32>,
+    },
+    /// Points evenly spaced on a line, which makes flat Minkowski differences.
+    Collinear {
+        from: (f32, f32),
+        step: (f32, f32),
+        count: u8,
+    },
+    Point((f32, f32)),
+}
+
+#[derive(Arbitrary, Debug)]
+pub struct Posed {
+    pub shape: Shape,
+    pub position: (f32, f32),
+}
+
+impl Shape {
+    pub fn build(&self) -> ShapeKind {
+        match *self {
+            Shape::Circle { center, radius } => Circle::new(point(center), radius).into(),
+            Shape::Aabb { from, to } => Aabb::new(point(from), point(to)).into(),
+            Shape::Polygon {
+                center,
+                radius,
+                ref angles,
+            } => {
+                let mut angles: Vec<_> =
+                    angles.iter().map(|a| a.fract().abs() * FULL_TURN).collect();
+                angles.sort_by(f32::total_cmp);
+                let points: Vec<_> = angles
+                    .iter()
+                    .map(|a| point((center.0 + radius * a.cos(), center.1 + radius * a.sin())))
+                    .collect();
+                if points.is_empty() {
+                    point(center).into()
+                } else {
+                    ConvexPolygon::new(points).into()
+                }
+            }
+            Shape::Collinear { from, step, count } => {
+                let points = (0..count.max(1))
+                    .map(|i| point((from.0 + step.0 * i as f32, from.1 + step.1 * i as f32)))
+                    .collect();
+                ConvexPolygon::new(points).into()
+            }
+            Shape::Point(p) => point(p).into(),
+        }
+    }
+
+    /// Whether the shape is one the library promises correct results for.
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            Shape::Circle { center, radius } => {
+                within(center) && within((radius, 0.)) && radius >= 0.
+            }
+            Shape::Aabb { from, to } => {
+                within(from) && within(to) && from.0 <= to.0 && from.1 <= to.1
+            }
+            Shape::Polygon {
+                center,
+                radius,
+                ref angles,
+            } => {
+                within(center)
+                    && within((radius, 0.))
+                    && radius >= 0.
+                    && angles.iter().all(|a| a.is_finite())
+            }
+            Shape::Collinear { from, step, count } => {
+                within(from)
+                    && within((
+                        from.0 + step.0 * count as f32,
+                        from.1 + step.1 * count as f32,
+                    ))
+            }
+            Shape::Point(p) => within(p),
+        }
+    }
+
+    /// Largest absolute coordinate of the shape, which the precision of the results depends on.
+    fn extent(&self) -> f32 {
+        let max = |(x, y): (f32, f32)| x.abs().max(y.abs());
+        match *self {
+            Shape::Circle { center, radius } | Shape::Polygon { center, radius, .. } => {
+                max(center) + radius.abs()
+            }
+            Shape::Aabb { from, to } => max(from).max(max(to)),
+            Shape::Collinear { from, step, count } => max(from).max(max((
+                from.0 + step.0 * count as f32,
+                from.1 + step.1 * count as f32,
+            ))),
+            Shape::Point(p) => max(p),
+        }
+    }
+}
+
+impl Posed {
+    pub fn position(&self) -> Point<f32> {
+        point(self.position)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        within(self.position) && self.shape.is_valid()
+    }
+}
+
+/// How far apart results may be because of rounding, for a query between `a` and `b`.
+pub fn tolerance(a: &Posed, b: &Posed) -> f32 {
+    let extent = |p: &Posed| p.shape.extent() + p.position.0.abs().max(p.position.1.abs());
+    1e-4 * (1. + extent(a).max(extent(b)))
+}
+
+fn point((x, y): (f32, f32)) -> Point<f32> {
+    Point::new(x, y)
+}
+
+fn within((x, y): (f32, f32)) -> bool {
+    x.abs() <= LIMIT && y.abs() <= LIMIT
+}
@@ -0,0 +1,114 @@
+use crate::shapes::{Aabb, Circle};
+use crate::{Point, Vector};
+
+pub fn circle_circle_collides(
+    (a, a_pos): (&Circle, Point<f32>),
+    (b, b_pos): (&Circle, Point<f32>),
+) -> bool {
+    let diff = (b_pos + b.center.coords) - (a_pos + a.center.coords);
+    let radii = a.radius + b.radius;
+    diff.norm_squared() <= radii * radii
+}
+
+/// Penetration normal and depth like `epa::solve`, or `None` if the circles don't collide.
+pub fn circle_circle(
+    (a, a_pos): (&Circle, Point<f32>),
+    (b, b_pos): (&Circle, Point<f32>),
+) -> Option<(Vector<f32>, f32)> {
+    let diff = (b_pos + b.center.coords) - (a_pos + a.center.coords);
+    let distance = diff.norm();
+    let depth = a.radius + b.radius - distance;
+    if depth < 0. {
+        return None;
+    }
+    let normal = if distance == 0. {
+        Vector::new(1., 0.)
+    } else {
+        diff / distance
+    };
+    Some((normal, depth))
+}
+
+pub fn aabb_aabb_collides(
+    (a, a_pos): (&Aabb, Point<f32>),
+    (b, b_pos): (&Aabb, Point<f32>),
+) -> bool {
+    let (a_from, a_to) = (a_pos + a.from.coords, a_pos + a.to.coords);
+    let (b_from, b_to) = (b_pos + b.from.coords, b_pos + b.to.coords);
+    a_from.x <= b_to.x && b_from.x <= a_to.x && a_from.y <= b_to.y && b_from.y <= a_to.y
+}
+
+/// Penetration normal and depth like `epa::solve`, or `None` if the boxes don't collide.
+pub fn aabb_aabb(
+    (a, a_pos): (&Aabb, Point<f32>),
+    (b, b_pos): (&Aabb, Point<f32>),
+) -> Option<(Vector<f32>, f32)> {
+    let (a_from, a_to) = (a_pos + a.from.coords, a_pos + a.to.coords);
+    let (b_from, b_to) = (b_pos + b.from.coords, b_pos + b.to.coords);
+    let x = a_to.x.min(b_to.x) - a_from.x.max(b_from.x);
+    let y = a_to.y.min(b_to.y) - a_from.y.max(b_from.y);
+    if x < 0. || y < 0. {
+        return None;
+    }
+    let diff = (b_from + b_to.coords) - (a_from + a_to.coords);
+    Some(if x < y {
+        (Vector::new(sign(diff.x), 0.), x)
+    } else {
+        (Vector::new(0., sign(diff.y)), y)
+    })
+}
+
+pub fn aabb_circle_collides(
+    (a, a_pos): (&Aabb, Point<f32>),
+    (b, b_pos): (&Circle, Point<f32>),
+) -> bool {
+    let center = b_pos + b.center.coords - a_pos.coords;
+    let closest = clamp(a, center);
+    (center - closest).norm_squared() <= b.radius * b.radius
+}
+
+/// Penetration normal and depth like `epa::solve`, or `None` if the shapes don't collide.
+pub fn aabb_circle(
+    (a, a_pos): (&Aabb, Point<f32>),
+    (b, b_pos): (&Circle, Point<f32>),
+) -> Option<(Vector<f32>, f32)> {
+    let center = b_pos + b.center.coords - a_pos.coords;
+    let closest = clamp(a, center);
+    if closest != center {
+        let diff = center - closest;
+        let distance = diff.norm();
+        let depth = b.radius - distance;
+        return if depth < 0. {
+            None
+        } else {
+            Some((diff / distance, depth))
+        };
+    }
+    // Center is inside, so push out through the closest side.
+    let sides = [
+        (Vector::new(0., -1.), center.y - a.from.y),
+        (Vector::new(1., 0.), a.to.x - center.x),
+        (Vector::new(0., 1.), a.to.y - center.y),
+        (Vector::new(-1., 0.), center.x - a.from.x),
+    ];
+    let &(normal, distance) = sides
+        .iter()
+        .min_by(|(_, d1), (_, d2)| d1.total_cmp(d2))
+        .unwrap();
+    Some((normal, b.radius + distance))
+}
+
+fn clamp(aabb: &Aabb, point: Point<f32>) -> Point<f32> {
+    Point::new(
+        point.x.max(aabb.from.x).min(aabb.to.x),
+        point.y.max(aabb.from.y).min(aabb.to.y),
+    )
+}
+
+fn sign(value: f32) -> f32 {
+    if value < 0. {
+        -1.
+    } else {
+        1.
+    }
+}
@@ -0,0 +1,144 @@
+use crate::gjk;
+use crate::shapes::{Aabb, Circle, Shape};
+use crate::Point;
+
+use alloc::vec::Vec;
+
+/// Number of pairs processed at once by the circle and box batches.
+///
+/// The lanes are plain arrays laid out so that the compiler can turn each step into SIMD instructions.
+pub const LANES: usize = 8;
+
+/// Tests every posed shape against `other` with GJK.
+pub fn collides_many<S1, S2>(shapes: &[(&S1, Point<f32>)], other: (&S2, Point<f32>)) -> Vec<bool>
+where
+    S1: Shape + ?Sized,
+    S2: Shape + ?Sized,
+{
+    shapes
+        .iter()
+        .map(|&shape| gjk::collides(shape, other))
+        .collect()
+}
+
+/// Tests every circle, placed at the matching pose, against `other`.
+pub fn circles_circle(
+    circles: &[Circle],
+    poses: &[Point<f32>],
+    (other, other_pos): (&Circle, Point<f32>),
+) -> Vec<bool> {
+    let center = other_pos + other.center.coords;
+    let radius = other.radius;
+    batch(
+        circles,
+        poses,
+        |lanes: &CircleLanes, out: &mut [bool; LANES]| {
+            for (i, out) in out.iter_mut().enumerate() {
+                let dx = lanes.x[i] - center.x;
+                let dy = lanes.y[i] - center.y;
+                let radii = lanes.radius[i] + radius;
+                *out = dx * dx + dy * dy <= radii * radii;
+            }
+        },
+    )
+}
+
+/// Tests every circle, placed at the matching pose, against `other`.
+pub fn circles_aabb(
+    circles: &[Circle],
+    poses: &[Point<f32>],
+    (other, other_pos): (&Aabb, Point<f32>),
+) -> Vec<bool> {
+    let from = other_pos + other.from.coords;
+    let to = other_pos + other.to.coords;
+    batch(
+        circles,
+        poses,
+        |lanes: &CircleLanes, out: &mut [bool; LANES]| {
+            for (i, out) in out.iter_mut().enumerate() {
+                let dx = lanes.x[i] - lanes.x[i].max(from.x).min(to.x);
+                let dy = lanes.y[i] - lanes.y[i].max(from.y).min(to.y);
+                *out = dx * dx + dy * dy <= lanes.radius[i] * lanes.radius[i];
+            }
+        },
+    )
+}
+
+/// Tests every box, placed at the matching pose, against `other`.
+pub fn aabbs_aabb(
+    aabbs: &[Aabb],
+    poses: &[Point<f32>],
+    (other, other_pos): (&Aabb, Point<f32>),
+) -> Vec<bool> {
+    let from = other_pos + other.from.coords;
+    let to = other_pos + other.to.coords;
+    batch(
+        aabbs,
+        poses,
+        |lanes: &AabbLanes, out: &mut [bool; LANES]| {
+            for (i, out) in out.iter_mut().enumerate() {
+                // `&` instead of `&&` keeps the comparisons branch free so they vectorize.
+                *out = (lanes.from_x[i] <= to.x)
+                    & (from.x <= lanes.to_x[i])
+                    & (lanes.from_y[i] <= to.y)
+                    & (from.y <= lanes.to_y[i]);
+            }
+        },
+    )
+}
+
+/// Shapes of a batch split into one array per coordinate.
+trait Lanes<S>: Default {
+    fn set(&mut self, lane: usize, shape: &S, pos: Point<f32>);
+}
+
+#[derive(Default)]
+struct CircleLanes {
+    x: [f32; LANES],
+    y: [f32; LANES],
+    radius: [f32; LANES],
+}
+
+impl Lanes<Circle> for CircleLanes {
+    fn set(&mut self, lane: usize, circle: &Circle, pos: Point<f32>) {
+        self.x[lane] = pos.x + circle.center.x;
+        self.y[lane] = pos.y + circle.center.y;
+        self.radius[lane] = circle.radius;
+    }
+}
+
+#[derive(Default)]
+struct AabbLanes {
+    from_x: [f32; LANES],
+    from_y: [f32; LANES],
+    to_x: [f32; LANES],
+    to_y: [f32; LANES],
+}
+
+impl Lanes<Aabb> for AabbLanes {
+    fn set(&mut self, lane: usize, aabb: &Aabb, pos: Point<f32>) {
+        self.from_x[lane] = pos.x + aabb.from.x;
+        self.from_y[lane] = pos.y + aabb.from.y;
+        self.to_x[lane] = pos.x + aabb.to.x;
+        self.to_y[lane] = pos.y + aabb.to.y;
+    }
+}
+
+fn batch<S, L, F>(shapes: &[S], poses: &[Point<f32>], test: F) -> Vec<bool>
+where
+    L: Lanes<S>,
+    F: Fn(&L, &mut [bool; LANES]),
+{
+    assert_eq!(shapes.len(), poses.len(), "Every shape needs a pose");
+    let mut results = Vec::with_capacity(shapes.len());
+    let mut lanes = L::default();
+    let mut out = [false; LANES];
+    for (shapes, poses) in shapes.chunks(LANES).zip(poses.chunks(LANES)) {
+        for (lane, (shape, pos)) in shapes.iter().zip(poses).enumerate() {
+            lanes.set(lane, shape, *pos);
+        }
+        test(&lanes, &mut out);
+        results.extend_from_slice(&out[..shapes.len()]);
+    }
+    results
+}
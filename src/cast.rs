@@ -0,0 +1,107 @@
+use crate::epa;
+use crate::gjk;
+use crate::shapes::{support, Shape};
+use crate::{Point, Vector};
+
+use nalgebra::zero;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+const TOLERANCE: f32 = 0.0001;
+const MAX_ITERATIONS: usize = 32;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CastHit {
+    /// Fraction of the motion that can be travelled before touching.
+    pub toi: f32,
+    /// Surface normal of the hit shape, pointing towards the moving shape.
+    pub normal: Vector<f32>,
+}
+
+/// Sweeps `a` along `motion` and finds the first time it touches `b`.
+///
+/// The query works on the Minkowski difference of `b` and `a` swept over a fraction of the motion,
+/// which only grows as the fraction grows, so the time of impact can be bisected without tunneling.
+///
+/// Shapes that touch at the start are only hit if `motion` goes further into the contact.
+pub fn cast<S1, S2>(
+    a: (&S1, Point<f32>),
+    motion: Vector<f32>,
+    b: (&S2, Point<f32>),
+) -> Option<CastHit>
+where
+    S1: Shape + ?Sized,
+    S2: Shape + ?Sized,
+{
+    if !swept_collides(a, motion, b, 1.) {
+        return None;
+    }
+    let (mut lo, mut hi) = (0., 1.);
+    if !swept_collides(a, motion, b, 0.) {
+        let length = motion.norm();
+        for _ in 0..MAX_ITERATIONS {
+            if (hi - lo) * length < TOLERANCE {
+                break;
+            }
+            let mid = (lo + hi) / 2.;
+            if swept_collides(a, motion, b, mid) {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+    } else {
+        hi = 0.;
+    }
+    let swept = |dir| swept_support(a, motion * hi, b, dir);
+    let (_, simplex) = gjk::collides_with(start(a, motion * hi, b), swept);
+    let (normal, _, _) = epa::solve_with(simplex, swept);
+    if hi == 0. && motion.dot(&normal) <= 0. {
+        return None;
+    }
+    Some(CastHit {
+        toi: lo,
+        normal: -normal,
+    })
+}
+
+fn swept_collides<S1, S2>(
+    a: (&S1, Point<f32>),
+    motion: Vector<f32>,
+    b: (&S2, Point<f32>),
+    fraction: f32,
+) -> bool
+where
+    S1: Shape + ?Sized,
+    S2: Shape + ?Sized,
+{
+    let motion = motion * fraction;
+    gjk::collides_with(start(a, motion, b), |dir| swept_support(a, motion, b, dir)).0
+}
+
+fn start<S1, S2>(a: (&S1, Point<f32>), motion: Vector<f32>, b: (&S2, Point<f32>)) -> Vector<f32>
+where
+    S1: Shape + ?Sized,
+    S2: Shape + ?Sized,
+{
+    (a.1 + a.0.start() + motion / 2.) - (b.1 + b.0.start())
+}
+
+fn swept_support<S1, S2>(
+    a: (&S1, Point<f32>),
+    motion: Vector<f32>,
+    b: (&S2, Point<f32>),
+    dir: Vector<f32>,
+) -> Vector<f32>
+where
+    S1: Shape + ?Sized,
+    S2: Shape + ?Sized,
+{
+    let sweep = if motion.dot(&dir) > 0. {
+        motion
+    } else {
+        zero()
+    };
+    support(a, b, dir) + sweep
+}
@@ -0,0 +1,89 @@
+use crate::cross;
+use crate::shapes::{Aabb, Circle, ConvexPolygon, Shape};
+use crate::Point;
+
+use alloc::{vec, vec::Vec};
+
+/// Clips `subject` with `clip` using the Sutherland–Hodgman algorithm.
+///
+/// Both polygons can be wound either way. The result is empty if they don't overlap.
+pub fn clip(subject: &ConvexPolygon, clip: &ConvexPolygon) -> ConvexPolygon {
+    let orientation = clip.signed_area();
+    if orientation == 0. {
+        return ConvexPolygon::new(vec![]);
+    }
+    let inside = |from: Point<f32>, to: Point<f32>, p: Point<f32>| {
+        cross(to - from, p - from) * orientation >= 0.
+    };
+    let mut output = subject.points.clone();
+    let edges = clip
+        .points
+        .iter()
+        .zip(clip.points.iter().skip(1).chain(clip.points.first()));
+    for (&from, &to) in edges {
+        if output.is_empty() {
+            break;
+        }
+        let input = output;
+        output = Vec::with_capacity(input.len() + 1);
+        let prevs = input.iter().cycle().skip(input.len() - 1);
+        for (&prev, &cur) in prevs.zip(&input) {
+            match (inside(from, to, prev), inside(from, to, cur)) {
+                (true, true) => output.push(cur),
+                (true, false) => output.push(line_intersection(prev, cur, from, to)),
+                (false, true) => {
+                    output.push(line_intersection(prev, cur, from, to));
+                    output.push(cur);
+                }
+                (false, false) => {}
+            }
+        }
+    }
+    ConvexPolygon::new(output)
+}
+
+/// Intersection of two posed shapes as a polygon in world space.
+///
+/// Circles are approximated by polygons within `tolerance`.
+/// Returns `None` if either shape isn't a `Circle`, `Aabb` or `ConvexPolygon`.
+pub fn intersection(
+    (a, a_pos): (&dyn Shape, Point<f32>),
+    (b, b_pos): (&dyn Shape, Point<f32>),
+    tolerance: f32,
+) -> Option<ConvexPolygon> {
+    let a = to_polygon(a, a_pos, tolerance)?;
+    let b = to_polygon(b, b_pos, tolerance)?;
+    Some(clip(&a, &b))
+}
+
+/// Area of the intersection of two posed shapes, see `intersection`.
+pub fn overlap_area(
+    a: (&dyn Shape, Point<f32>),
+    b: (&dyn Shape, Point<f32>),
+    tolerance: f32,
+) -> Option<f32> {
+    intersection(a, b, tolerance).map(|polygon| polygon.area())
+}
+
+fn to_polygon(shape: &dyn Shape, pos: Point<f32>, tolerance: f32) -> Option<ConvexPolygon> {
+    let mut polygon = if let Some(circle) = shape.downcast_ref::<Circle>() {
+        circle.to_polygon(tolerance)
+    } else if let Some(aabb) = shape.downcast_ref::<Aabb>() {
+        aabb.to_polygon()
+    } else if let Some(polygon) = shape.downcast_ref::<ConvexPolygon>() {
+        polygon.clone()
+    } else {
+        return None;
+    };
+    for p in &mut polygon.points {
+        *p += pos.coords;
+    }
+    Some(polygon)
+}
+
+fn line_intersection(a: Point<f32>, b: Point<f32>, from: Point<f32>, to: Point<f32>) -> Point<f32> {
+    let ab = b - a;
+    let edge = to - from;
+    let t = cross(from - a, edge) / cross(ab, edge);
+    a + ab * t
+}
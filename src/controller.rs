@@ -0,0 +1,175 @@
+use crate::cast::{cast, CastHit};
+use crate::epa;
+use crate::gjk;
+use crate::shapes::Shape;
+use crate::{Point, Vector};
+
+use nalgebra::{zero, Real};
+
+use alloc::{vec, vec::Vec};
+
+#[derive(Clone, Debug)]
+pub struct CharacterController {
+    /// Direction that counts as up when deciding what is ground.
+    pub up: Vector<f32>,
+    /// Steepest slope in radians that the character can stand on and climb.
+    pub max_slope: f32,
+    /// How far down the character is pulled to stay on the ground.
+    pub snap_distance: f32,
+    /// Gap kept between the character and the geometry it touches.
+    pub skin: f32,
+    /// How many times the motion is allowed to slide along a surface.
+    pub max_iterations: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Movement {
+    /// Translation that was actually achieved, including depenetration.
+    pub motion: Vector<f32>,
+    pub grounded: bool,
+    /// Normals of every surface that was collided with.
+    pub normals: Vec<Vector<f32>>,
+}
+
+impl Default for CharacterController {
+    fn default() -> Self {
+        CharacterController {
+            up: Vector::new(0., 1.),
+            max_slope: 45f32.to_radians(),
+            snap_distance: 0.1,
+            skin: 0.001,
+            max_iterations: 4,
+        }
+    }
+}
+
+impl CharacterController {
+    pub fn new(up: Vector<f32>, max_slope: f32) -> Self {
+        CharacterController {
+            up: up.normalize(),
+            max_slope,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_walkable(&self, normal: Vector<f32>) -> bool {
+        normal.dot(&self.up) >= Real::cos(self.max_slope)
+    }
+
+    /// Moves `character` by `desired` against static `obstacles`, sliding along whatever it hits.
+    pub fn move_and_slide<S, O>(
+        &self,
+        (character, pos): (&S, Point<f32>),
+        desired: Vector<f32>,
+        obstacles: &[(&O, Point<f32>)],
+    ) -> Movement
+    where
+        S: Shape + ?Sized,
+        O: Shape + ?Sized,
+    {
+        let mut movement = Movement {
+            motion: self.depenetrate((character, pos), obstacles),
+            grounded: false,
+            normals: vec![],
+        };
+        let mut remaining = desired;
+        for _ in 0..self.max_iterations {
+            if remaining == zero() {
+                break;
+            }
+            let cur = pos + movement.motion;
+            let hit = match first_hit((character, cur), remaining, obstacles) {
+                Some(hit) => hit,
+                None => {
+                    movement.motion += remaining;
+                    break;
+                }
+            };
+            movement.motion += self.advance(remaining, hit.toi);
+            movement.normals.push(hit.normal);
+            let walkable = self.is_walkable(hit.normal);
+            movement.grounded |= walkable;
+
+            remaining *= 1. - hit.toi;
+            remaining -= hit.normal * remaining.dot(&hit.normal);
+            // Walls and too steep slopes must not lift the character up.
+            if !walkable && desired.dot(&self.up) <= 0. {
+                let lift = remaining.dot(&self.up);
+                if lift > 0. {
+                    remaining -= self.up * lift;
+                }
+            }
+        }
+
+        if !movement.grounded && desired.dot(&self.up) <= 0. && self.snap_distance > 0. {
+            let cur = pos + movement.motion;
+            let snap = -self.up * self.snap_distance;
+            if let Some(hit) = first_hit((character, cur), snap, obstacles) {
+                if self.is_walkable(hit.normal) {
+                    movement.motion += self.advance(snap, hit.toi);
+                    movement.normals.push(hit.normal);
+                    movement.grounded = true;
+                }
+            }
+        }
+        movement
+    }
+
+    /// Finds a translation that pushes `character` out of every obstacle it overlaps.
+    pub fn depenetrate<S, O>(
+        &self,
+        (character, pos): (&S, Point<f32>),
+        obstacles: &[(&O, Point<f32>)],
+    ) -> Vector<f32>
+    where
+        S: Shape + ?Sized,
+        O: Shape + ?Sized,
+    {
+        let mut offset = zero();
+        for _ in 0..self.max_iterations {
+            let mut resolved = true;
+            for &(obstacle, obstacle_pos) in obstacles {
+                let a = (character, pos + offset);
+                let b = (obstacle, obstacle_pos);
+                let (collides, simplex) = gjk::collides_internal(a, b);
+                if !collides {
+                    continue;
+                }
+                let (normal, depth, _) = epa::solve_internal(a, b, simplex);
+                // Exact contact gets the skin too, or casts from there would start out touching.
+                if depth >= 0. {
+                    offset -= normal * (depth + self.skin);
+                    resolved = false;
+                }
+            }
+            if resolved {
+                break;
+            }
+        }
+        offset
+    }
+
+    fn advance(&self, motion: Vector<f32>, toi: f32) -> Vector<f32> {
+        let length = motion.norm() * toi - self.skin;
+        if length > 0. {
+            motion.normalize() * length
+        } else {
+            zero()
+        }
+    }
+}
+
+fn first_hit<S, O>(
+    character: (&S, Point<f32>),
+    motion: Vector<f32>,
+    obstacles: &[(&O, Point<f32>)],
+) -> Option<CastHit>
+where
+    S: Shape + ?Sized,
+    O: Shape + ?Sized,
+{
+    obstacles
+        .iter()
+        .filter_map(|&obstacle| cast(character, motion, obstacle))
+        .min_by(|a, b| a.toi.total_cmp(&b.toi))
+}
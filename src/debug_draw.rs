@@ -0,0 +1,114 @@
+use crate::cast::CastHit;
+use crate::dispatch::Contact;
+use crate::outline::outline;
+use crate::shapes::{Aabb, Circle, ConvexPolygon, Shape, ShapeKind};
+use crate::{Point, Vector};
+
+use alloc::format;
+use alloc::vec::Vec;
+
+/// Red, green, blue and alpha.
+pub type Color = [u8; 4];
+
+/// Drawing primitives of a host renderer, in the same space as the shapes.
+pub trait DebugRenderer {
+    fn line(&mut self, from: Point<f32>, to: Point<f32>, color: Color);
+    fn circle(&mut self, center: Point<f32>, radius: f32, color: Color);
+    /// Outline of a closed polygon.
+    fn polygon(&mut self, points: &[Point<f32>], color: Color);
+    fn point(&mut self, point: Point<f32>, color: Color);
+    fn text(&mut self, at: Point<f32>, text: &str, color: Color);
+}
+
+/// Tolerance of the outline of shapes that aren't built in, relative to their width.
+const OUTLINE_TOLERANCE: f32 = 1e-3;
+
+/// Draws the outline of a posed shape.
+///
+/// Built-in shapes are drawn exactly and other shapes are outlined from their support points.
+pub fn draw_shape<R>(renderer: &mut R, (shape, pos): (&dyn Shape, Point<f32>), color: Color)
+where
+    R: DebugRenderer + ?Sized,
+{
+    if let Some(circle) = shape.downcast_ref::<Circle>() {
+        draw_circle(renderer, circle, pos, color);
+    } else if let Some(aabb) = shape.downcast_ref::<Aabb>() {
+        draw_aabb(renderer, aabb, pos, color);
+    } else if let Some(polygon) = shape.downcast_ref::<ConvexPolygon>() {
+        draw_points(renderer, &polygon.points, pos, color);
+    } else if let Some(point) = shape.downcast_ref::<Point<f32>>() {
+        renderer.point(pos + point.coords, color);
+    } else if let Some(kind) = shape.downcast_ref::<ShapeKind>() {
+        match kind {
+            ShapeKind::Circle(circle) => draw_circle(renderer, circle, pos, color),
+            ShapeKind::Aabb(aabb) => draw_aabb(renderer, aabb, pos, color),
+            ShapeKind::ConvexPolygon(polygon) => draw_points(renderer, &polygon.points, pos, color),
+            ShapeKind::ConvexHull(hull) => draw_points(renderer, hull.points(), pos, color),
+            ShapeKind::Point(point) => renderer.point(pos + point.coords, color),
+        }
+    } else {
+        let width = shape.farthest_in_dir(Vector::new(1., 0.)).x
+            - shape.farthest_in_dir(Vector::new(-1., 0.)).x;
+        let points = outline((shape, pos), width * OUTLINE_TOLERANCE);
+        draw_points(renderer, &points, Point::origin(), color);
+    }
+}
+
+/// Draws the contact point, the penetration along the normal and the depth.
+pub fn draw_contact<R>(renderer: &mut R, contact: &Contact, color: Color)
+where
+    R: DebugRenderer + ?Sized,
+{
+    let half = contact.normal * (contact.depth / 2.);
+    renderer.line(contact.point - half, contact.point + half, color);
+    renderer.point(contact.point, color);
+    renderer.text(contact.point, &format!("{:.3}", contact.depth), color);
+}
+
+/// Draws a cast of the posed shape along `motion`: the path its origin takes,
+/// the shape where it stops and the normal of the surface it hit.
+pub fn draw_cast_hit<R>(
+    renderer: &mut R,
+    (shape, pos): (&dyn Shape, Point<f32>),
+    motion: Vector<f32>,
+    hit: &CastHit,
+    color: Color,
+) where
+    R: DebugRenderer + ?Sized,
+{
+    let stop = pos + motion * hit.toi;
+    renderer.line(pos, stop, color);
+    draw_shape(renderer, (shape, stop), color);
+    let at = stop + shape.farthest_in_dir(-hit.normal);
+    renderer.line(at, at + hit.normal * motion.norm() * 0.25, color);
+    renderer.text(at, &format!("toi {:.3}", hit.toi), color);
+}
+
+fn draw_circle<R>(renderer: &mut R, circle: &Circle, pos: Point<f32>, color: Color)
+where
+    R: DebugRenderer + ?Sized,
+{
+    renderer.circle(pos + circle.center.coords, circle.radius, color);
+}
+
+fn draw_aabb<R>(renderer: &mut R, aabb: &Aabb, pos: Point<f32>, color: Color)
+where
+    R: DebugRenderer + ?Sized,
+{
+    draw_points(renderer, &aabb.corners(), pos, color);
+}
+
+fn draw_points<R>(renderer: &mut R, points: &[Point<f32>], pos: Point<f32>, color: Color)
+where
+    R: DebugRenderer + ?Sized,
+{
+    match points {
+        [] => {}
+        [point] => renderer.point(pos + point.coords, color),
+        [from, to] => renderer.line(pos + from.coords, pos + to.coords, color),
+        _ => {
+            let points: Vec<_> = points.iter().map(|p| pos + p.coords).collect();
+            renderer.polygon(&points, color);
+        }
+    }
+}
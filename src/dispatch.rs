@@ -0,0 +1,193 @@
+use crate::analytic;
+use crate::epa;
+use crate::gjk;
+use crate::sat;
+use crate::shapes::{Aabb, Circle, ConvexPolygon, Shape, ShapeKind};
+use crate::{Point, Vector};
+
+use alloc::borrow::Cow;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Contact {
+    /// Points from the first shape towards the second one.
+    pub normal: Vector<f32>,
+    pub depth: f32,
+    /// Middle of the overlapping region along the normal.
+    pub point: Point<f32>,
+}
+
+/// Like `gjk::collides`, but uses closed form tests for pairs of circles and boxes.
+pub fn collides(a: (&dyn Shape, Point<f32>), b: (&dyn Shape, Point<f32>)) -> bool {
+    collides_as(a, kind(a.0), b, kind(b.0))
+}
+
+/// Like `collides`, but matches on the shapes instead of downcasting them.
+pub fn collides_kinds(a: (&ShapeKind, Point<f32>), b: (&ShapeKind, Point<f32>)) -> bool {
+    collides_as(a, Kind::of(a.0), b, Kind::of(b.0))
+}
+
+/// Penetration normal and depth like `epa::solve`, or `None` if the shapes don't collide.
+///
+/// Pairs of circles and boxes are solved in closed form, other pairs of polygons, boxes and circles use SAT,
+/// and everything else goes through GJK and EPA.
+pub fn penetration(
+    a: (&dyn Shape, Point<f32>),
+    b: (&dyn Shape, Point<f32>),
+) -> Option<(Vector<f32>, f32)> {
+    penetration_as(a, kind(a.0), b, kind(b.0))
+}
+
+/// Like `penetration`, but matches on the shapes instead of downcasting them.
+pub fn penetration_kinds(
+    a: (&ShapeKind, Point<f32>),
+    b: (&ShapeKind, Point<f32>),
+) -> Option<(Vector<f32>, f32)> {
+    penetration_as(a, Kind::of(a.0), b, Kind::of(b.0))
+}
+
+/// Penetration with a contact point, or `None` if the shapes don't collide.
+pub fn contact(a: (&dyn Shape, Point<f32>), b: (&dyn Shape, Point<f32>)) -> Option<Contact> {
+    contact_as(a, kind(a.0), b, kind(b.0))
+}
+
+/// Like `contact`, but matches on the shapes instead of downcasting them.
+pub fn contact_kinds(a: (&ShapeKind, Point<f32>), b: (&ShapeKind, Point<f32>)) -> Option<Contact> {
+    contact_as(a, Kind::of(a.0), b, Kind::of(b.0))
+}
+
+/// Built-in shape that has fast paths.
+#[derive(Clone, Copy)]
+enum Kind<'a> {
+    Circle(&'a Circle),
+    Aabb(&'a Aabb),
+    ConvexPolygon(&'a ConvexPolygon),
+    Other,
+}
+
+impl<'a> Kind<'a> {
+    fn of(shape: &'a ShapeKind) -> Self {
+        match shape {
+            ShapeKind::Circle(circle) => Kind::Circle(circle),
+            ShapeKind::Aabb(aabb) => Kind::Aabb(aabb),
+            ShapeKind::ConvexPolygon(polygon) => Kind::ConvexPolygon(polygon),
+            ShapeKind::ConvexHull(_) | ShapeKind::Point(_) => Kind::Other,
+        }
+    }
+
+    fn polygon(self) -> Option<Cow<'a, [Point<f32>]>> {
+        match self {
+            Kind::ConvexPolygon(polygon) if polygon.points.len() >= 2 => {
+                Some(Cow::Borrowed(&polygon.points))
+            }
+            Kind::Aabb(aabb) => Some(Cow::Owned(aabb.corners().to_vec())),
+            _ => None,
+        }
+    }
+}
+
+fn kind(shape: &dyn Shape) -> Kind<'_> {
+    if let Some(circle) = shape.downcast_ref::<Circle>() {
+        Kind::Circle(circle)
+    } else if let Some(aabb) = shape.downcast_ref::<Aabb>() {
+        Kind::Aabb(aabb)
+    } else if let Some(polygon) = shape.downcast_ref::<ConvexPolygon>() {
+        Kind::ConvexPolygon(polygon)
+    } else if let Some(shape) = shape.downcast_ref::<ShapeKind>() {
+        Kind::of(shape)
+    } else {
+        Kind::Other
+    }
+}
+
+fn collides_as<S1, S2>(a: (&S1, Point<f32>), ka: Kind, b: (&S2, Point<f32>), kb: Kind) -> bool
+where
+    S1: Shape + ?Sized,
+    S2: Shape + ?Sized,
+{
+    match (ka, kb) {
+        (Kind::Circle(ca), Kind::Circle(cb)) => {
+            analytic::circle_circle_collides((ca, a.1), (cb, b.1))
+        }
+        (Kind::Aabb(aa), Kind::Aabb(ab)) => analytic::aabb_aabb_collides((aa, a.1), (ab, b.1)),
+        (Kind::Aabb(aa), Kind::Circle(cb)) => analytic::aabb_circle_collides((aa, a.1), (cb, b.1)),
+        (Kind::Circle(ca), Kind::Aabb(ab)) => analytic::aabb_circle_collides((ab, b.1), (ca, a.1)),
+        _ => gjk::collides(a, b),
+    }
+}
+
+fn penetration_as<S1, S2>(
+    a: (&S1, Point<f32>),
+    ka: Kind,
+    b: (&S2, Point<f32>),
+    kb: Kind,
+) -> Option<(Vector<f32>, f32)>
+where
+    S1: Shape + ?Sized,
+    S2: Shape + ?Sized,
+{
+    match (ka, kb) {
+        (Kind::Circle(ca), Kind::Circle(cb)) => {
+            return analytic::circle_circle((ca, a.1), (cb, b.1))
+        }
+        (Kind::Aabb(aa), Kind::Aabb(ab)) => return analytic::aabb_aabb((aa, a.1), (ab, b.1)),
+        (Kind::Aabb(aa), Kind::Circle(cb)) => return analytic::aabb_circle((aa, a.1), (cb, b.1)),
+        (Kind::Circle(ca), Kind::Aabb(ab)) => {
+            return analytic::aabb_circle((ab, b.1), (ca, a.1))
+                .map(|(normal, depth)| (-normal, depth))
+        }
+        _ => {}
+    }
+    match (ka.polygon(), kb.polygon(), ka, kb) {
+        (Some(pa), Some(pb), _, _) => return sat::points_points((&pa, a.1), (&pb, b.1)),
+        (Some(pa), None, _, Kind::Circle(cb)) => return sat::points_circle((&pa, a.1), (cb, b.1)),
+        (None, Some(pb), Kind::Circle(ca), _) => {
+            return sat::points_circle((&pb, b.1), (ca, a.1))
+                .map(|(normal, depth)| (-normal, depth))
+        }
+        _ => {}
+    }
+    let (collides, simplex) = gjk::collides_internal(a, b);
+    if collides {
+        Some(epa::solve(a, b, simplex))
+    } else {
+        None
+    }
+}
+
+fn contact_as<S1, S2>(
+    a: (&S1, Point<f32>),
+    ka: Kind,
+    b: (&S2, Point<f32>),
+    kb: Kind,
+) -> Option<Contact>
+where
+    S1: Shape + ?Sized,
+    S2: Shape + ?Sized,
+{
+    let (normal, depth) = penetration_as(a, ka, b, kb)?;
+    let point = match (ka, kb) {
+        (Kind::Circle(ca), Kind::Circle(_)) => {
+            a.1 + ca.center.coords + normal * (ca.radius - depth / 2.)
+        }
+        (Kind::Aabb(aa), Kind::Aabb(ab)) => {
+            let from = Point::new(
+                (a.1.x + aa.from.x).max(b.1.x + ab.from.x),
+                (a.1.y + aa.from.y).max(b.1.y + ab.from.y),
+            );
+            let to = Point::new(
+                (a.1.x + aa.to.x).min(b.1.x + ab.to.x),
+                (a.1.y + aa.to.y).min(b.1.y + ab.to.y),
+            );
+            from + (to - from) / 2.
+        }
+        _ => a.1 + a.0.farthest_in_dir(normal) - normal * (depth / 2.),
+    };
+    Some(Contact {
+        normal,
+        depth,
+        point,
+    })
+}
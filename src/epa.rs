@@ -2,73 +2,312 @@ use crate::shapes::support;
 use crate::shapes::Shape;
 use crate::simplex::Simplex;
 use crate::simplex::Winding;
-use crate::{Point, Vector};
+use crate::trace::{Event, Termination, Trace};
+use crate::{cross, to_f32, Num, Point, Vector};
 
 use nalgebra::zero;
 
-const TOLERANCE: f32 = 0.00001;
+use alloc::collections::BinaryHeap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
 
-struct Edge {
-    distance: f32,
-    normal: Vector<f32>,
-    index: usize,
+/// Relative to the depth, so shapes of any size take as many iterations to converge.
+const TOLERANCE: f32 = 0.00003;
+
+/// Support points can't be more precise than this relative to the coordinates they're computed from,
+/// which matters for shallow penetrations and for small shapes far from the origin.
+const ROUNDING: f32 = 4. * f32::EPSILON;
+
+/// Keeps shapes with NaN coordinates from growing the polytope forever.
+const MAX_ITERATIONS: usize = 1024;
+
+/// Number type that EPA can run on, with how precisely it converges.
+pub(crate) trait Tolerance: Num {
+    /// How far beyond the closest edge the support point along its normal may be for EPA to stop.
+    fn slack(depth: Self, scale: Self, support: Vector<Self>) -> Self;
+}
+
+impl Tolerance for f32 {
+    fn slack(depth: f32, scale: f32, support: Vector<f32>) -> f32 {
+        TOLERANCE * depth + ROUNDING * (scale + support.norm())
+    }
+}
+
+/// Edge of the polytope between the points at `from` and `to`.
+struct Edge<N: Num> {
+    distance: N,
+    normal: Vector<N>,
+    from: usize,
+    to: usize,
+}
+
+impl<N: Num> PartialEq for Edge<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
 }
 
-pub fn solve(
-    a: (&impl Shape, Point<f32>),
-    b: (&impl Shape, Point<f32>),
+impl<N: Num> Eq for Edge<N> {}
+
+impl<N: Num> PartialOrd for Edge<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Closer edges are greater, so that `BinaryHeap` pops the closest one first.
+impl<N: Num> Ord for Edge<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance.total_cmp(&self.distance)
+    }
+}
+
+/// Polytope kept as a ring of linked points, so that expanding it doesn't move the other points
+/// and the closest edge comes from a heap instead of checking every edge.
+struct Polytope<N: Num> {
+    points: Vec<Vector<N>>,
+    next: Vec<usize>,
+    edges: BinaryHeap<Edge<N>>,
+    winding: Winding,
+}
+
+impl<N: Num> Polytope<N> {
+    fn new(points: Vec<Vector<N>>, winding: Winding) -> Self {
+        let count = points.len();
+        let mut polytope = Polytope {
+            points,
+            next: (1..count).chain(Some(0)).collect(),
+            edges: BinaryHeap::with_capacity(count),
+            winding,
+        };
+        for from in 0..count {
+            polytope.push_edge(from, polytope.next[from]);
+        }
+        polytope
+    }
+
+    /// Degenerate edges are left out, they can't be the closest one.
+    fn push_edge(&mut self, from: usize, to: usize) {
+        let (a, b) = (self.points[from], self.points[to]);
+        if let Some(normal) = N::unit(perp(b - a, self.winding)) {
+            self.edges.push(Edge {
+                distance: normal.dot(&a),
+                normal,
+                from,
+                to,
+            });
+        }
+    }
+
+    /// Replaces `edge` with the two edges through `point`.
+    fn expand(&mut self, edge: &Edge<N>, point: Vector<N>) {
+        let index = self.points.len();
+        self.points.push(point);
+        self.next.push(edge.to);
+        self.next[edge.from] = index;
+        self.push_edge(edge.from, index);
+        self.push_edge(index, edge.to);
+    }
+
+    /// Points in winding order.
+    fn to_vec(&self) -> Vec<Vector<N>> {
+        let mut index = 0;
+        let mut points = Vec::with_capacity(self.points.len());
+        for _ in 0..self.points.len() {
+            points.push(self.points[index]);
+            index = self.next[index];
+        }
+        points
+    }
+}
+
+pub fn solve<S1, S2>(
+    a: (&S1, Point<f32>),
+    b: (&S2, Point<f32>),
     simplex: Simplex,
-) -> (Vector<f32>, f32) {
+) -> (Vector<f32>, f32)
+where
+    S1: Shape + ?Sized,
+    S2: Shape + ?Sized,
+{
     let (v, d, _) = solve_internal(a, b, simplex);
     (v, d)
 }
 
-pub fn solve_internal(
-    a: (&impl Shape, Point<f32>),
-    b: (&impl Shape, Point<f32>),
-    mut simplex: Simplex,
-) -> (Vector<f32>, f32, Vec<Vector<f32>>) {
-    if let Simplex::Line(from, to) = simplex {
-        let support = support(a, b, perp(to - from, Winding::Left));
-        simplex.add(support);
+pub fn solve_internal<S1, S2>(
+    a: (&S1, Point<f32>),
+    b: (&S2, Point<f32>),
+    simplex: Simplex,
+) -> (Vector<f32>, f32, Vec<Vector<f32>>)
+where
+    S1: Shape + ?Sized,
+    S2: Shape + ?Sized,
+{
+    solve_traced(a, b, simplex, &mut ())
+}
+
+/// Like `solve_internal`, but reports every step to `trace`.
+pub fn solve_traced<S1, S2, T>(
+    a: (&S1, Point<f32>),
+    b: (&S2, Point<f32>),
+    simplex: Simplex,
+    trace: &mut T,
+) -> (Vector<f32>, f32, Vec<Vector<f32>>)
+where
+    S1: Shape + ?Sized,
+    S2: Shape + ?Sized,
+    T: Trace,
+{
+    let scale = (a.1 + a.0.start())
+        .coords
+        .norm()
+        .max((b.1 + b.0.start()).coords.norm());
+    run(simplex, |dir| support(a, b, dir), scale, trace)
+}
+
+/// Runs EPA over an arbitrary Minkowski difference given by its support function.
+pub fn solve_with<F>(simplex: Simplex, support: F) -> (Vector<f32>, f32, Vec<Vector<f32>>)
+where
+    F: Fn(Vector<f32>) -> Vector<f32>,
+{
+    run(simplex, support, 0., &mut ())
+}
+
+/// EPA loop shared by every number type.
+///
+/// `scale` is how far from the origin the shapes are, which limits how precise their support points are.
+pub(crate) fn run<N, F, T>(
+    simplex: Simplex<N>,
+    support: F,
+    scale: N,
+    trace: &mut T,
+) -> (Vector<N>, N, Vec<Vector<N>>)
+where
+    N: Tolerance,
+    F: Fn(Vector<N>) -> Vector<N>,
+    T: Trace,
+{
+    let mut simplex = flatten(&simplex);
+    // GJK can end with a point or a line when the shapes touch, so grow those into a triangle first.
+    while simplex.len() < 3 {
+        let dirs = match simplex[..] {
+            [_] => vec![
+                Vector::new(N::one(), N::zero()),
+                Vector::new(N::zero(), N::one()),
+            ],
+            [from, to] => vec![perp(to - from, Winding::Left)],
+            _ => unreachable!(),
+        };
+        let found = dirs
+            .into_iter()
+            .flat_map(|dir| vec![dir, -dir])
+            .find_map(|dir| {
+                trace.record(Event::Direction(to_f32(dir)));
+                let support = support(dir);
+                trace.record(Event::Support(to_f32(support)));
+                if widens(&simplex, support) {
+                    Some(support)
+                } else {
+                    None
+                }
+            });
+        match found {
+            Some(support) => simplex.push(support),
+            None => {
+                // The difference has no area, so the origin is on its boundary.
+                let normal = match simplex[..] {
+                    [from, to] => N::unit(perp(to - from, Winding::Left)),
+                    _ => None,
+                };
+                trace.finish(Termination::Touching, 0);
+                let normal = normal.unwrap_or_else(|| Vector::new(N::one(), N::zero()));
+                return (normal, N::zero(), simplex);
+            }
+        }
+    }
+    let winding = if cross(simplex[1] - simplex[0], simplex[2] - simplex[0]) < N::zero() {
+        Winding::Right
+    } else {
+        Winding::Left
+    };
+    trace.record(Event::PolytopeSize(simplex.len()));
+    if trace.enabled() {
+        trace.record(Event::Polytope(
+            simplex.iter().copied().map(to_f32).collect(),
+        ));
     }
-    let winding = simplex.winding();
-    let mut simplex: Vec<_> = simplex.into_iter().collect();
-    loop {
-        let edge = find_closest_edge(&simplex, winding);
-        let support = support(a, b, edge.normal);
+    let mut polytope = Polytope::new(simplex, winding);
+    // Shallowest penetration found so far, in case the polytope doesn't converge.
+    let mut best = (zero(), N::MAX);
+    let mut iterations = 0;
+    while let Some(edge) = polytope.edges.pop() {
+        if iterations == MAX_ITERATIONS {
+            break;
+        }
+        iterations += 1;
+        trace.record(Event::ClosestEdge {
+            from: to_f32(polytope.points[edge.from]),
+            to: to_f32(polytope.points[edge.to]),
+            normal: to_f32(edge.normal),
+            distance: edge.distance.to_f32(),
+        });
+        trace.record(Event::Direction(to_f32(edge.normal)));
+        let support = support(edge.normal);
+        trace.record(Event::Support(to_f32(support)));
         let depth = support.dot(&edge.normal);
-        if depth - edge.distance < TOLERANCE {
-            return (edge.normal, depth, simplex);
-        } else {
-            simplex.insert(edge.index, support);
+        // Done once the support point is on the closest edge, give or take the tolerance.
+        if depth - edge.distance <= N::slack(depth, scale, support) {
+            trace.finish(Termination::Converged, iterations);
+            return (edge.normal, depth, polytope.to_vec());
+        }
+        if depth < best.1 {
+            best = (edge.normal, depth);
+        }
+        polytope.expand(&edge, support);
+        trace.record(Event::PolytopeSize(polytope.points.len()));
+        if trace.enabled() {
+            trace.record(Event::Polytope(
+                polytope.to_vec().into_iter().map(to_f32).collect(),
+            ));
         }
     }
+    trace.finish(Termination::IterationLimit, iterations);
+    (best.0, best.1, polytope.to_vec())
 }
 
-fn find_closest_edge(simplex: &[Vector<f32>], winding: Winding) -> Edge {
-    let mut closest = Edge {
-        distance: ::std::f32::MAX,
-        normal: zero(),
-        index: 0,
-    };
-    for i in 0..simplex.len() {
-        let j = if i + 1 == simplex.len() { 0 } else { i + 1 };
-        let a = simplex[i];
-        let b = simplex[j];
-        let edge = b - a;
-        let normal = perp(edge, winding).normalize();
-        let dist = normal.dot(&a);
-        if dist < closest.distance {
-            closest.distance = dist;
-            closest.normal = normal;
-            closest.index = j;
+/// Distinct points of the simplex, with a collinear triangle reduced to its longest side.
+fn flatten<N: Num>(simplex: &Simplex<N>) -> Vec<Vector<N>> {
+    let mut points: Vec<Vector<N>> = Vec::with_capacity(8);
+    for point in simplex {
+        if !points.contains(&point) {
+            points.push(point);
+        }
+    }
+    if let [a, b, c] = points[..] {
+        if cross(b - a, c - a) == N::zero() {
+            let length = |(from, to): (Vector<N>, Vector<N>)| (to - from).dot(&(to - from));
+            let longest = [(a, b), (b, c), (c, a)]
+                .iter()
+                .copied()
+                .max_by(|&x, &y| length(x).total_cmp(&length(y)))
+                .unwrap();
+            points = vec![longest.0, longest.1];
         }
     }
-    closest
+    points
+}
+
+/// Whether adding `point` to a point or a line gives it a dimension more.
+fn widens<N: Num>(simplex: &[Vector<N>], point: Vector<N>) -> bool {
+    match *simplex {
+        [p] => point != p,
+        [from, to] => cross(to - from, point - from) != N::zero(),
+        _ => false,
+    }
 }
 
-fn perp(vector: Vector<f32>, winding: Winding) -> Vector<f32> {
+fn perp<N: Num>(vector: Vector<N>, winding: Winding) -> Vector<N> {
     if let Winding::Right = winding {
         Vector::new(-vector.y, vector.x)
     } else {
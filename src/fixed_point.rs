@@ -0,0 +1,194 @@
+//! GJK and EPA on fixed-point numbers, for results that are bit-identical on every platform.
+//!
+//! Everything here is integer arithmetic, including the square roots used to normalize directions.
+//! Coordinates should stay within a few thousand units so that dot products can't overflow.
+
+use crate::epa::{self, Tolerance};
+use crate::gjk;
+use crate::simplex::Simplex;
+use crate::Num;
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// Fixed-point number with 32 integer and 32 fractional bits.
+pub type Scalar = ::fixed::types::I32F32;
+
+pub type Vector = crate::Vector<Scalar>;
+
+pub type Point = crate::Point<Scalar>;
+
+/// 2^-16, a bit more than the precision left after normalizing a direction.
+const TOLERANCE: Scalar = Scalar::from_bits(1 << 16);
+
+impl Num for Scalar {
+    const MAX: Self = Scalar::MAX;
+
+    fn is_nan(self) -> bool {
+        false
+    }
+
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+
+    fn unit(vector: Vector) -> Option<Vector> {
+        if vector.x == 0 && vector.y == 0 {
+            None
+        } else {
+            Some(normalize(vector))
+        }
+    }
+
+    fn to_f32(self) -> f32 {
+        self.to_num()
+    }
+}
+
+/// Converges to an absolute tolerance, as fixed-point numbers are equally precise at any size.
+impl Tolerance for Scalar {
+    fn slack(_depth: Self, _scale: Self, _support: Vector) -> Self {
+        TOLERANCE
+    }
+}
+
+/// Fixed-point counterpart of `shapes::Shape`.
+pub trait Shape {
+    fn start(&self) -> Vector;
+    fn farthest_in_dir(&self, dir: Vector) -> Vector;
+}
+
+#[derive(Clone, Debug)]
+pub struct Circle {
+    pub center: Point,
+    pub radius: Scalar,
+}
+
+impl Circle {
+    pub fn new(center: Point, radius: Scalar) -> Self {
+        Circle { center, radius }
+    }
+}
+
+impl Shape for Circle {
+    fn start(&self) -> Vector {
+        self.center.coords
+    }
+    fn farthest_in_dir(&self, dir: Vector) -> Vector {
+        self.center.coords + normalize(dir) * self.radius
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Aabb {
+    pub from: Point,
+    pub to: Point,
+}
+
+impl Aabb {
+    pub fn new(from: Point, to: Point) -> Self {
+        Aabb { from, to }
+    }
+}
+
+impl Shape for Aabb {
+    fn start(&self) -> Vector {
+        self.from.coords + (self.to.coords - self.from.coords) / Scalar::from_num(2)
+    }
+    fn farthest_in_dir(&self, dir: Vector) -> Vector {
+        let x = if dir.x < 0 { self.from.x } else { self.to.x };
+        let y = if dir.y < 0 { self.from.y } else { self.to.y };
+        Vector::new(x, y)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ConvexPolygon {
+    pub points: Vec<Point>,
+}
+
+impl ConvexPolygon {
+    pub fn new(points: Vec<Point>) -> Self {
+        ConvexPolygon { points }
+    }
+}
+
+impl Shape for ConvexPolygon {
+    fn start(&self) -> Vector {
+        self.points[0].coords
+    }
+    fn farthest_in_dir(&self, dir: Vector) -> Vector {
+        // Ties go to the first point, so the result doesn't depend on anything but the input.
+        let mut farthest = self.points[0].coords;
+        let mut max = farthest.dot(&dir);
+        for point in &self.points[1..] {
+            let dot = point.coords.dot(&dir);
+            if dot > max {
+                max = dot;
+                farthest = point.coords;
+            }
+        }
+        farthest
+    }
+}
+
+pub fn support<S1, S2>(a: (&S1, Point), b: (&S2, Point), dir: Vector) -> Vector
+where
+    S1: Shape + ?Sized,
+    S2: Shape + ?Sized,
+{
+    (a.1 + a.0.farthest_in_dir(dir)) - (b.1 + b.0.farthest_in_dir(-dir))
+}
+
+pub fn collides<S1, S2>(a: (&S1, Point), b: (&S2, Point)) -> bool
+where
+    S1: Shape + ?Sized,
+    S2: Shape + ?Sized,
+{
+    collides_internal(a, b).0
+}
+
+/// Runs the same GJK as `gjk::collides_internal`, whose search directions are edge perpendiculars
+/// that can't overflow like triple products would.
+pub fn collides_internal<S1, S2>(a: (&S1, Point), b: (&S2, Point)) -> (bool, Simplex<Scalar>)
+where
+    S1: Shape + ?Sized,
+    S2: Shape + ?Sized,
+{
+    let start = (a.1 + a.0.start()) - (b.1 + b.0.start());
+    let (collides, simplex, _) = gjk::run(start, |dir| support(a, b, dir), &mut ());
+    (collides, simplex)
+}
+
+pub fn penetration<S1, S2>(a: (&S1, Point), b: (&S2, Point)) -> Option<(Vector, Scalar)>
+where
+    S1: Shape + ?Sized,
+    S2: Shape + ?Sized,
+{
+    let (collides, simplex) = collides_internal(a, b);
+    if collides {
+        Some(solve(a, b, simplex))
+    } else {
+        None
+    }
+}
+
+/// Fixed-point counterpart of `epa::solve`, running the same EPA. The normal points from `a` towards `b`.
+pub fn solve<S1, S2>(a: (&S1, Point), b: (&S2, Point), simplex: Simplex<Scalar>) -> (Vector, Scalar)
+where
+    S1: Shape + ?Sized,
+    S2: Shape + ?Sized,
+{
+    let (normal, depth, _) = epa::run(simplex, |dir| support(a, b, dir), Scalar::ZERO, &mut ());
+    (normal, depth)
+}
+
+/// Scales the vector down to its largest component before taking the length, so squaring can't overflow.
+fn normalize(vector: Vector) -> Vector {
+    let scale = vector.x.abs().max(vector.y.abs());
+    if scale == 0 {
+        return vector;
+    }
+    let vector = vector / scale;
+    vector / (vector.x * vector.x + vector.y * vector.y).sqrt()
+}
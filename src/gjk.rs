@@ -3,7 +3,11 @@ use crate::shapes::support;
 
 use crate::shapes::Shape;
 use crate::simplex::Simplex;
-use crate::{Point, Vector};
+use crate::trace::{Event, Termination, Trace};
+use crate::{cross, to_f32, Num, Perp, Point, Vector};
+
+const TOLERANCE: f32 = 0.000001;
+const MAX_ITERATIONS: usize = 64;
 
 /// a x (b x c)
 pub fn triple_product(a: Vector<f32>, b: Vector<f32>, c: Vector<f32>) -> Vector<f32> {
@@ -23,36 +27,115 @@ where
     S1: Shape + ?Sized,
     S2: Shape + ?Sized,
 {
-    let mut cur = (a.1 + a.0.start()) - (b.1 + b.0.start());
+    collides_traced(a, b, &mut ())
+}
+
+/// Like `collides_internal`, but reports every step to `trace`.
+pub fn collides_traced<S1, S2, T>(
+    a: (&S1, Point<f32>),
+    b: (&S2, Point<f32>),
+    trace: &mut T,
+) -> (bool, Simplex)
+where
+    S1: Shape + ?Sized,
+    S2: Shape + ?Sized,
+    T: Trace,
+{
+    let start = (a.1 + a.0.start()) - (b.1 + b.0.start());
+    let (collides, simplex, _) = run(start, |dir| support(a, b, dir), trace);
+    (collides, simplex)
+}
+
+/// Like `collides_internal`, but starts from the search direction `seed` that a previous query
+/// ended with, and replaces it with the one this query ends with.
+///
+/// If the shapes were separated, that direction separates them, and shapes that only moved a little
+/// since usually stay separated along it, which takes a single iteration to confirm. Colliding shapes
+/// take three support points to enclose the origin either way, so there's nothing to gain from
+/// seeding with a previous simplex.
+///
+/// Seed a new pair with `Vector::zeros()`, or with the offset from `a` to `b` to start the way
+/// `collides_internal` does.
+pub fn collides_seeded<S1, S2>(
+    a: (&S1, Point<f32>),
+    b: (&S2, Point<f32>),
+    seed: &mut Vector<f32>,
+) -> (bool, Simplex)
+where
+    S1: Shape + ?Sized,
+    S2: Shape + ?Sized,
+{
+    let (collides, simplex, dir) = run(-*seed, |dir| support(a, b, dir), &mut ());
+    *seed = dir;
+    (collides, simplex)
+}
+
+/// Runs GJK over an arbitrary Minkowski difference given by its support function.
+pub fn collides_with<F>(start: Vector<f32>, support: F) -> (bool, Simplex)
+where
+    F: Fn(Vector<f32>) -> Vector<f32>,
+{
+    let (collides, simplex, _) = run(start, support, &mut ());
+    (collides, simplex)
+}
+
+/// GJK loop shared by every number type, returning the last search direction too.
+pub(crate) fn run<N, F, T>(
+    start: Vector<N>,
+    support: F,
+    trace: &mut T,
+) -> (bool, Simplex<N>, Vector<N>)
+where
+    N: Num,
+    F: Fn(Vector<N>) -> Vector<N>,
+    T: Trace,
+{
+    let mut cur = start;
     if cur == zero() {
-        cur = Vector::new(1., 0.);
+        cur = Vector::new(N::one(), N::zero());
     }
-    let mut simplex = Simplex::Point(support(a, b, cur));
+    trace.record(Event::Direction(to_f32(cur)));
+    let mut simplex = Simplex::Point(support(cur));
+    trace.record(Event::Support(to_f32(*simplex.last())));
+    trace.record(Event::Simplex(simplex.map(to_f32)));
     cur = -cur;
-    while cur != zero() {
-        let support = support(a, b, cur);
+    for i in 0..MAX_ITERATIONS {
+        if cur == zero() {
+            trace.finish(Termination::Touching, i);
+            return (true, simplex, cur);
+        }
+        trace.record(Event::Direction(to_f32(cur)));
+        let support = support(cur);
+        trace.record(Event::Support(to_f32(support)));
         simplex.add(support);
-        if support.dot(&cur) < 0. {
-            return (false, simplex);
+        trace.record(Event::Simplex(simplex.map(to_f32)));
+        // NaN can't be enclosed either, so it counts as separated rather than as a collision.
+        let progress = support.dot(&cur);
+        if progress < N::zero() || N::is_nan(progress) {
+            trace.finish(Termination::Separated, i + 1);
+            return (false, simplex, cur);
         } else if expand(&mut simplex, &mut cur) {
-            return (true, simplex);
+            trace.finish(Termination::Enclosed, i + 1);
+            return (true, simplex, cur);
         }
+        trace.record(Event::Simplex(simplex.map(to_f32)));
     }
-    (true, simplex)
+    trace.finish(Termination::IterationLimit, MAX_ITERATIONS);
+    (false, simplex, cur)
 }
 
-fn expand(simplex: &mut Simplex, cur: &mut Vector<f32>) -> bool {
+fn expand<N: Num>(simplex: &mut Simplex<N>, cur: &mut Vector<N>) -> bool {
     match *simplex {
         Simplex::Triangle(b, c, a) => {
             let ao = -a;
             let ab = b - a;
             let ac = c - a;
-            let ab_perp = triple_product(ac, ab, ab);
-            let ac_perp = triple_product(ab, ac, ac);
-            if ab_perp.dot(&ao) > 0. {
+            let ab_perp = perp_towards(ab, -ac);
+            let ac_perp = perp_towards(ac, -ab);
+            if ab_perp.dot(&ao) > N::zero() {
                 *simplex = Simplex::Line(b, a);
                 *cur = ab_perp;
-            } else if ac_perp.dot(&ao) > 0. {
+            } else if ac_perp.dot(&ao) > N::zero() {
                 *simplex = Simplex::Line(c, a);
                 *cur = ac_perp;
             } else {
@@ -62,9 +145,109 @@ fn expand(simplex: &mut Simplex, cur: &mut Vector<f32>) -> bool {
         Simplex::Line(b, a) => {
             let ao = -a;
             let ab = b - a;
-            *cur = triple_product(ab, ao, ab);
+            if cross(ab, ao) != N::zero() {
+                *cur = perp_towards(ab, ao);
+            } else if ab.dot(&ao) >= N::zero() && ab.dot(&ao) <= ab.dot(&ab) {
+                // The origin is on the segment, so the shapes touch.
+                *cur = zero();
+            } else {
+                // In line with the origin but not around it, which a seeded first point can be.
+                *simplex = Simplex::Point(a);
+                *cur = ao;
+            }
         }
         _ => unreachable!(),
     }
     false
 }
+
+/// Perpendicular of `edge` on the side of `dir`.
+///
+/// Unlike triple products, this doesn't lose precision to cancellation when the simplex is far
+/// bigger than its distance to the origin.
+fn perp_towards<N: Num>(edge: Vector<N>, dir: Vector<N>) -> Vector<N> {
+    let perp = edge.perpendicular();
+    if cross(edge, dir) < N::zero() {
+        -perp
+    } else {
+        perp
+    }
+}
+
+/// Finds the point of a Minkowski difference closest to the origin, or `None` if it contains the origin.
+pub fn closest_point_with<F>(start: Vector<f32>, support: F) -> Option<Vector<f32>>
+where
+    F: Fn(Vector<f32>) -> Vector<f32>,
+{
+    let start = if start == zero() {
+        Vector::new(1., 0.)
+    } else {
+        start
+    };
+    let mut simplex = Simplex::Point(support(-start));
+    let mut cur = *simplex.last();
+    for _ in 0..MAX_ITERATIONS {
+        if cur == zero() {
+            return if on_boundary(&simplex, &support) {
+                Some(cur)
+            } else {
+                None
+            };
+        }
+        let support = support(-cur);
+        if cur.norm_squared() - cur.dot(&support) <= TOLERANCE * cur.norm_squared()
+            || (&simplex).into_iter().any(|v| v == support)
+        {
+            return Some(cur);
+        }
+        simplex.add(support);
+        cur = closest_on_simplex(&mut simplex)?;
+    }
+    Some(cur)
+}
+
+/// Checks if the origin lying on the simplex means that it's on the boundary of the whole shape.
+fn on_boundary<F>(simplex: &Simplex, support: F) -> bool
+where
+    F: Fn(Vector<f32>) -> Vector<f32>,
+{
+    if let Simplex::Line(a, b) = *simplex {
+        let dir = b - a;
+        let dir = Vector::new(-dir.y, dir.x);
+        [dir, -dir].iter().any(|&d| support(d).dot(&d) <= 0.)
+    } else {
+        true
+    }
+}
+
+fn closest_on_simplex(simplex: &mut Simplex) -> Option<Vector<f32>> {
+    let (closest, reduced) = match *simplex {
+        Simplex::Triangle(a, b, c) => {
+            let sides = [cross(b - a, -a), cross(c - b, -b), cross(a - c, -c)];
+            if sides.iter().all(|s| *s > 0.) || sides.iter().all(|s| *s < 0.) {
+                return None;
+            }
+            [(a, b), (b, c), (c, a)]
+                .iter()
+                .map(|&(from, to)| closest_on_segment(from, to))
+                .min_by(|(v1, _), (v2, _)| v1.norm_squared().total_cmp(&v2.norm_squared()))
+                .unwrap()
+        }
+        Simplex::Line(a, b) => closest_on_segment(a, b),
+        Simplex::Point(a) => (a, Simplex::Point(a)),
+    };
+    *simplex = reduced;
+    Some(closest)
+}
+
+fn closest_on_segment(a: Vector<f32>, b: Vector<f32>) -> (Vector<f32>, Simplex) {
+    let ab = b - a;
+    let t = -a.dot(&ab) / ab.norm_squared();
+    if t <= 0. || t.is_nan() {
+        (a, Simplex::Point(a))
+    } else if t >= 1. {
+        (b, Simplex::Point(b))
+    } else {
+        (a + ab * t, Simplex::Line(a, b))
+    }
+}
@@ -1,12 +1,48 @@
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 use nalgebra as na;
 
-use std::fmt::Debug;
-use std::ops::Neg;
+use num_traits::{One, Zero};
 
+#[cfg(feature = "alloc")]
+use core::cmp::Ordering;
+use core::fmt::Debug;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+pub mod analytic;
+#[cfg(feature = "alloc")]
+pub mod batch;
+#[cfg(feature = "alloc")]
+pub mod cast;
+#[cfg(feature = "alloc")]
+pub mod clip;
+#[cfg(feature = "alloc")]
+pub mod controller;
+#[cfg(feature = "alloc")]
+pub mod debug_draw;
+#[cfg(feature = "alloc")]
+pub mod dispatch;
+#[cfg(feature = "alloc")]
 pub mod epa;
+#[cfg(feature = "fixed")]
+pub mod fixed_point;
 pub mod gjk;
+#[cfg(feature = "alloc")]
+pub mod outline;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+#[cfg(feature = "alloc")]
+pub mod sat;
+#[cfg(feature = "alloc")]
+pub mod sdf;
 pub mod shapes;
 pub mod simplex;
+pub mod trace;
 
 pub type Vector<T> = na::Vector2<T>;
 
@@ -24,3 +60,80 @@ where
         Vector::new(-self.y, self.x)
     }
 }
+
+/// Number type that GJK and EPA run on, `f32` or the fixed-point `Scalar` of `fixed_point`.
+pub(crate) trait Num:
+    na::Scalar
+    + PartialOrd
+    + Zero
+    + One
+    + Neg<Output = Self>
+    + Add<Output = Self>
+    + AddAssign
+    + Sub<Output = Self>
+    + SubAssign
+    + Mul<Output = Self>
+    + MulAssign
+    + Div<Output = Self>
+    + DivAssign
+{
+    // EPA, which needs a heap, is the only user of these.
+    #[cfg(feature = "alloc")]
+    const MAX: Self;
+
+    fn is_nan(self) -> bool;
+
+    #[cfg(feature = "alloc")]
+    fn total_cmp(&self, other: &Self) -> Ordering;
+
+    /// Unit vector along `vector`, or `None` for zero length.
+    #[cfg(feature = "alloc")]
+    fn unit(vector: Vector<Self>) -> Option<Vector<Self>>;
+
+    /// Traces are recorded in `f32`, whatever the query runs on.
+    fn to_f32(self) -> f32;
+}
+
+impl Num for f32 {
+    #[cfg(feature = "alloc")]
+    const MAX: Self = f32::MAX;
+
+    fn is_nan(self) -> bool {
+        f32::is_nan(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        f32::total_cmp(self, other)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn unit(vector: Vector<Self>) -> Option<Vector<Self>> {
+        unit(vector)
+    }
+
+    fn to_f32(self) -> f32 {
+        self
+    }
+}
+
+/// Converts `vector` for recording it in a trace.
+pub(crate) fn to_f32<N: Num>(vector: Vector<N>) -> Vector<f32> {
+    vector.map(N::to_f32)
+}
+
+/// Z component of the cross product of two vectors on the plane.
+pub(crate) fn cross<N: Num>(a: Vector<N>, b: Vector<N>) -> N {
+    a.x * b.y - a.y * b.x
+}
+
+/// Unit vector along `vector`, or `None` for zero length.
+///
+/// Scales by the largest component first, so that tiny vectors don't underflow to a zero length.
+pub(crate) fn unit(vector: Vector<f32>) -> Option<Vector<f32>> {
+    let scale = vector.x.abs().max(vector.y.abs());
+    if scale == 0. || !scale.is_finite() {
+        return None;
+    }
+    Some((vector / scale).normalize())
+}
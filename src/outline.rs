@@ -0,0 +1,110 @@
+use crate::shapes::{support, Shape};
+use crate::{cross, Point, Vector};
+
+use alloc::vec::Vec;
+
+/// Limit on how many times a span of directions is halved, in case the tolerance can't be reached.
+const MAX_DEPTH: u32 = 16;
+
+/// Outline of a posed shape as a counterclockwise polyline.
+///
+/// No point of the shape's boundary is farther than `tolerance` outside of the polyline.
+pub fn outline<S>((shape, pos): (&S, Point<f32>), tolerance: f32) -> Vec<Point<f32>>
+where
+    S: Shape + ?Sized,
+{
+    outline_with(|dir| shape.farthest_in_dir(dir), tolerance)
+        .into_iter()
+        .map(|v| pos + v)
+        .collect()
+}
+
+/// Outline of the Minkowski difference of `a` and `b`, like `outline`.
+pub fn minkowski_outline<S1, S2>(
+    a: (&S1, Point<f32>),
+    b: (&S2, Point<f32>),
+    tolerance: f32,
+) -> Vec<Vector<f32>>
+where
+    S1: Shape + ?Sized,
+    S2: Shape + ?Sized,
+{
+    outline_with(|dir| support(a, b, dir), tolerance)
+}
+
+/// Outlines an arbitrary convex shape given by its support function.
+///
+/// Starts from the four axis directions and, between neighbouring support points,
+/// keeps adding the support point along the outward normal of the line between them
+/// until it is within `tolerance` of that line.
+pub fn outline_with<F>(support: F, tolerance: f32) -> Vec<Vector<f32>>
+where
+    F: Fn(Vector<f32>) -> Vector<f32>,
+{
+    let dirs = [
+        Vector::new(1., 0.),
+        Vector::new(0., 1.),
+        Vector::new(-1., 0.),
+        Vector::new(0., -1.),
+    ];
+    let mut points = Vec::new();
+    for (i, &from) in dirs.iter().enumerate() {
+        let to = dirs[(i + 1) % dirs.len()];
+        let start = support(from);
+        push(&mut points, start);
+        refine(&support, start, support(to), tolerance, 0, &mut points);
+    }
+    if points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    remove_collinear(&mut points);
+    points
+}
+
+/// Removes points in the middle of straight edges, which ties in the support function can leave behind.
+fn remove_collinear(points: &mut Vec<Vector<f32>>) {
+    let mut i = 0;
+    while points.len() > 2 && i < points.len() {
+        let prev = points[(i + points.len() - 1) % points.len()];
+        let next = points[(i + 1) % points.len()];
+        let (a, b) = (points[i] - prev, next - points[i]);
+        if cross(a, b).abs() <= f32::EPSILON * a.norm() * b.norm() && a.dot(&b) >= 0. {
+            points.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+/// Adds the points strictly between the support points `from` and `to`.
+fn refine<F>(
+    support: &F,
+    from: Vector<f32>,
+    to: Vector<f32>,
+    tolerance: f32,
+    depth: u32,
+    points: &mut Vec<Vector<f32>>,
+) where
+    F: Fn(Vector<f32>) -> Vector<f32>,
+{
+    if from == to || depth == MAX_DEPTH {
+        return;
+    }
+    // The farthest point outside of the edge is the support point along its outward normal,
+    // which lies between `from` and `to` for a convex shape.
+    let edge = to - from;
+    let dir = Vector::new(edge.y, -edge.x).normalize();
+    let middle = support(dir);
+    if cross(middle - from, edge) <= tolerance * edge.norm() {
+        return;
+    }
+    refine(support, from, middle, tolerance, depth + 1, points);
+    push(points, middle);
+    refine(support, middle, to, tolerance, depth + 1, points);
+}
+
+fn push(points: &mut Vec<Vector<f32>>, point: Vector<f32>) {
+    if points.last() != Some(&point) {
+        points.push(point);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::epa;
+use crate::gjk;
+use crate::shapes::{Aabb, Shape};
+use crate::{Point, Vector};
+
+use alloc::vec::Vec;
+use rayon::prelude::*;
+
+/// Two posed shapes to test against each other.
+pub type Pair<'a, S1, S2> = ((&'a S1, Point<f32>), (&'a S2, Point<f32>));
+
+/// Tests every pair with GJK, spreading the pairs over rayon's thread pool.
+pub fn collides_pairs<S1, S2>(pairs: &[Pair<S1, S2>]) -> Vec<bool>
+where
+    S1: Shape + Sync + ?Sized,
+    S2: Shape + Sync + ?Sized,
+{
+    pairs
+        .par_iter()
+        .map(|&(a, b)| gjk::collides(a, b))
+        .collect()
+}
+
+/// Penetration normal and depth from GJK and EPA for every pair, or `None` for pairs that don't collide.
+pub fn penetrations<S1, S2>(pairs: &[Pair<S1, S2>]) -> Vec<Option<(Vector<f32>, f32)>>
+where
+    S1: Shape + Sync + ?Sized,
+    S2: Shape + Sync + ?Sized,
+{
+    pairs
+        .par_iter()
+        .map(|&(a, b)| {
+            let (collides, simplex) = gjk::collides_internal(a, b);
+            if collides {
+                Some(epa::solve(a, b, simplex))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Finds the indices of every pair of colliding shapes.
+///
+/// Bounding boxes are compared first and only overlapping ones are tested with GJK.
+/// Pairs are ordered by their first index and then by their second.
+pub fn colliding_pairs<S>(shapes: &[(&S, Point<f32>)]) -> Vec<(usize, usize)>
+where
+    S: Shape + Sync + ?Sized,
+{
+    let bounds: Vec<_> = shapes
+        .par_iter()
+        .map(|&(shape, pos)| {
+            let aabb = Aabb::bounding(shape);
+            Aabb::new(pos + aabb.from.coords, pos + aabb.to.coords)
+        })
+        .collect();
+    (0..shapes.len())
+        .into_par_iter()
+        .flat_map_iter(|i| {
+            let bounds = &bounds;
+            (i + 1..shapes.len())
+                .filter(move |&j| overlaps(&bounds[i], &bounds[j]))
+                .filter(move |&j| gjk::collides(shapes[i], shapes[j]))
+                .map(move |j| (i, j))
+        })
+        .collect()
+}
+
+fn overlaps(a: &Aabb, b: &Aabb) -> bool {
+    a.from.x <= b.to.x && b.from.x <= a.to.x && a.from.y <= b.to.y && b.from.y <= a.to.y
+}
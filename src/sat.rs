@@ -0,0 +1,113 @@
+use crate::shapes::{Circle, ConvexPolygon};
+use crate::{cross, Perp, Point, Vector};
+
+/// Penetration between two polygons using the separating axis theorem.
+///
+/// Returns `None` if the polygons are separated, otherwise the same normal and depth as `epa::solve`.
+/// Polygons need at least two points.
+pub fn polygon_polygon(
+    (a, a_pos): (&ConvexPolygon, Point<f32>),
+    (b, b_pos): (&ConvexPolygon, Point<f32>),
+) -> Option<(Vector<f32>, f32)> {
+    points_points((&a.points, a_pos), (&b.points, b_pos))
+}
+
+/// Penetration between a polygon and a circle using the separating axis theorem.
+pub fn polygon_circle(
+    (a, a_pos): (&ConvexPolygon, Point<f32>),
+    (b, b_pos): (&Circle, Point<f32>),
+) -> Option<(Vector<f32>, f32)> {
+    points_circle((&a.points, a_pos), (b, b_pos))
+}
+
+pub(crate) fn points_points(
+    (a, a_pos): (&[Point<f32>], Point<f32>),
+    (b, b_pos): (&[Point<f32>], Point<f32>),
+) -> Option<(Vector<f32>, f32)> {
+    let offset = b_pos - a_pos;
+    let mut best: Option<(Vector<f32>, f32)> = None;
+    for axis in axes(a).chain(axes(b)) {
+        let (a_min, a_max) = project(a, axis, 0.);
+        let (b_min, b_max) = project(b, axis, offset.dot(&axis));
+        best = Some(deepest(best, axis, (a_min, a_max), (b_min, b_max))?);
+    }
+    best
+}
+
+pub(crate) fn points_circle(
+    (a, a_pos): (&[Point<f32>], Point<f32>),
+    (b, b_pos): (&Circle, Point<f32>),
+) -> Option<(Vector<f32>, f32)> {
+    let center = b_pos + b.center.coords - a_pos.coords;
+    let closest = a
+        .iter()
+        .min_by(|p1, p2| {
+            let d1 = (center - *p1).norm_squared();
+            let d2 = (center - *p2).norm_squared();
+            d1.total_cmp(&d2)
+        })
+        .map(|p| center - p)
+        .filter(|v| v.norm_squared() > 0.)
+        .map(|v| v.normalize());
+    let mut best: Option<(Vector<f32>, f32)> = None;
+    for axis in axes(a).chain(closest) {
+        let (a_min, a_max) = project(a, axis, 0.);
+        let c = center.coords.dot(&axis);
+        best = Some(deepest(
+            best,
+            axis,
+            (a_min, a_max),
+            (c - b.radius, c + b.radius),
+        )?);
+    }
+    best
+}
+
+/// Edge normals, and also the edges themselves if the points are on a line, as then its ends can separate.
+fn axes(points: &[Point<f32>]) -> impl Iterator<Item = Vector<f32>> + '_ {
+    let edges = points
+        .iter()
+        .zip(points.iter().skip(1).chain(points.first()))
+        .map(|(from, to)| to - from)
+        .filter(|edge| edge.norm_squared() > 0.);
+    let flat = edges
+        .clone()
+        .zip(edges.clone().skip(1))
+        .all(|(edge, next)| cross(edge, next) == 0.);
+    edges
+        .clone()
+        .map(|edge| edge.perpendicular().normalize())
+        .chain(edges.filter(move |_| flat).map(|edge| edge.normalize()))
+}
+
+fn project(points: &[Point<f32>], axis: Vector<f32>, offset: f32) -> (f32, f32) {
+    points
+        .iter()
+        .map(|p| p.coords.dot(&axis) + offset)
+        .fold((f32::MAX, f32::MIN), |(min, max), d| {
+            (min.min(d), max.max(d))
+        })
+}
+
+/// Picks the shallower of the current best and the overlap along `axis`, or `None` if the axis separates.
+fn deepest(
+    best: Option<(Vector<f32>, f32)>,
+    axis: Vector<f32>,
+    (a_min, a_max): (f32, f32),
+    (b_min, b_max): (f32, f32),
+) -> Option<(Vector<f32>, f32)> {
+    let forward = a_max - b_min;
+    let backward = b_max - a_min;
+    let candidate = if forward < backward {
+        (axis, forward)
+    } else {
+        (-axis, backward)
+    };
+    if candidate.1 < 0. {
+        return None;
+    }
+    Some(match best {
+        Some(best) if best.1 <= candidate.1 => best,
+        _ => candidate,
+    })
+}
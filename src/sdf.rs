@@ -0,0 +1,53 @@
+use crate::shapes::Shape;
+use crate::{Point, Vector};
+
+use alloc::vec::Vec;
+
+/// Regularly spaced sample points, stored row by row starting from `origin`.
+#[derive(Clone, Debug)]
+pub struct Grid {
+    pub origin: Point<f32>,
+    pub cell_size: f32,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Grid {
+    pub fn new(origin: Point<f32>, cell_size: f32, width: usize, height: usize) -> Self {
+        Grid {
+            origin,
+            cell_size,
+            width,
+            height,
+        }
+    }
+
+    pub fn point(&self, x: usize, y: usize) -> Point<f32> {
+        self.origin + Vector::new(x as f32, y as f32) * self.cell_size
+    }
+}
+
+/// Signed distance to the union of the shapes.
+pub fn signed_distance<S>(shapes: &[(&S, Point<f32>)], point: Point<f32>) -> f32
+where
+    S: Shape + ?Sized,
+{
+    shapes
+        .iter()
+        .map(|&(shape, pos)| shape.signed_distance(point - pos.coords))
+        .fold(f32::MAX, f32::min)
+}
+
+/// Samples the signed distance field of the shapes at every point of the grid.
+pub fn bake<S>(shapes: &[(&S, Point<f32>)], grid: &Grid) -> Vec<f32>
+where
+    S: Shape + ?Sized,
+{
+    let mut buffer = Vec::with_capacity(grid.width * grid.height);
+    for y in 0..grid.height {
+        for x in 0..grid.width {
+            buffer.push(signed_distance(shapes, grid.point(x, y)));
+        }
+    }
+    buffer
+}
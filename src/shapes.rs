@@ -1,10 +1,94 @@
-use crate::{Perp, Point, Vector};
+#[cfg(feature = "alloc")]
+use crate::Perp;
+use crate::{cross, unit, Point, Vector};
+#[cfg(feature = "alloc")]
+use crate::{epa, gjk};
 
-use mopa::{mopafy, Any};
+use mopa::Any;
+use nalgebra::{zero, Real};
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, sync::Arc, vec, vec::Vec};
+use core::f32::consts::PI;
+#[cfg(feature = "serde")]
+use serde::{ser, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Most vertices `Circle::to_polygon` uses, however small the tolerance.
+#[cfg(feature = "alloc")]
+const MAX_CIRCLE_VERTICES: usize = 1024;
 
 pub trait Shape: Any {
     fn start(&self) -> Vector<f32>;
     fn farthest_in_dir(&self, dir: Vector<f32>) -> Vector<f32>;
+
+    fn contains_point(&self, point: Point<f32>) -> bool {
+        self.project_point(point).inside
+    }
+
+    /// Distance to the boundary of the shape, negative when inside.
+    fn signed_distance(&self, point: Point<f32>) -> f32 {
+        self.project_point(point).distance
+    }
+
+    /// Finds the closest point on the boundary of the shape.
+    ///
+    /// The default implementation only uses `farthest_in_dir`, so it can't tell which feature was hit.
+    /// It needs EPA for points inside, so without the `alloc` feature every shape has to implement this.
+    #[cfg(feature = "alloc")]
+    fn project_point(&self, point: Point<f32>) -> PointProjection {
+        let support = |dir| self.farthest_in_dir(dir) - point.coords;
+        let start = self.start() - point.coords;
+        if let Some(closest) = gjk::closest_point_with(start, support) {
+            return PointProjection::new(
+                point,
+                point + closest,
+                closest == zero(),
+                Feature::Unknown,
+            );
+        }
+        let (_, simplex) = gjk::collides_with(start, support);
+        let (normal, depth, _) = epa::solve_with(simplex, support);
+        PointProjection::new(point, point + normal * depth, true, Feature::Unknown)
+    }
+
+    /// Finds the closest point on the boundary of the shape.
+    #[cfg(not(feature = "alloc"))]
+    fn project_point(&self, point: Point<f32>) -> PointProjection;
+}
+
+/// Part of the shape boundary that a projected point lies on.
+///
+/// Vertices and edges are numbered like the points of a `ConvexPolygon`, edge `i` going from vertex `i` to `i + 1`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Feature {
+    Vertex(usize),
+    Edge(usize),
+    /// Smooth boundary of a round shape.
+    Boundary,
+    Unknown,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PointProjection {
+    pub point: Point<f32>,
+    /// Distance to the boundary, negative when inside.
+    pub distance: f32,
+    pub inside: bool,
+    pub feature: Feature,
+}
+
+impl PointProjection {
+    fn new(from: Point<f32>, point: Point<f32>, inside: bool, feature: Feature) -> Self {
+        let distance = (point - from).norm();
+        PointProjection {
+            point,
+            distance: if inside { -distance } else { distance },
+            inside,
+            feature,
+        }
+    }
 }
 
 pub fn support<S1, S2>(
@@ -21,7 +105,20 @@ where
     p1 - p2
 }
 
-mopafy!(Shape);
+/// Shape trait object that can be shared between threads, for example as `Box<SyncShape>` or `Arc<SyncShape>`.
+pub type SyncShape = dyn Shape + Send + Sync;
+
+// mopa's generated downcasts transmute raw pointers, which clippy flags.
+#[allow(clippy::transmute_ptr_to_ref)]
+mod mopafied {
+    use super::Shape;
+    use mopa::mopafy;
+
+    #[cfg(feature = "alloc")]
+    mopafy!(Shape, core = core, alloc = alloc);
+    #[cfg(not(feature = "alloc"))]
+    mopafy!(Shape, core = core);
+}
 
 impl Shape for Point<f32> {
     fn start(&self) -> Vector<f32> {
@@ -30,8 +127,12 @@ impl Shape for Point<f32> {
     fn farthest_in_dir(&self, _dir: Vector<f32>) -> Vector<f32> {
         self.coords
     }
+    fn project_point(&self, point: Point<f32>) -> PointProjection {
+        PointProjection::new(point, *self, point == *self, Feature::Vertex(0))
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl<T> Shape for Box<T>
 where
     T: Shape + ?Sized,
@@ -42,6 +143,37 @@ where
     fn farthest_in_dir(&self, dir: Vector<f32>) -> Vector<f32> {
         T::farthest_in_dir(self, dir)
     }
+    fn contains_point(&self, point: Point<f32>) -> bool {
+        T::contains_point(self, point)
+    }
+    fn signed_distance(&self, point: Point<f32>) -> f32 {
+        T::signed_distance(self, point)
+    }
+    fn project_point(&self, point: Point<f32>) -> PointProjection {
+        T::project_point(self, point)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Shape for Arc<T>
+where
+    T: Shape + ?Sized,
+{
+    fn start(&self) -> Vector<f32> {
+        T::start(self)
+    }
+    fn farthest_in_dir(&self, dir: Vector<f32>) -> Vector<f32> {
+        T::farthest_in_dir(self, dir)
+    }
+    fn contains_point(&self, point: Point<f32>) -> bool {
+        T::contains_point(self, point)
+    }
+    fn signed_distance(&self, point: Point<f32>) -> f32 {
+        T::signed_distance(self, point)
+    }
+    fn project_point(&self, point: Point<f32>) -> PointProjection {
+        T::project_point(self, point)
+    }
 }
 
 impl<T> Shape for &'static T
@@ -54,9 +186,19 @@ where
     fn farthest_in_dir(&self, dir: Vector<f32>) -> Vector<f32> {
         T::farthest_in_dir(self, dir)
     }
+    fn contains_point(&self, point: Point<f32>) -> bool {
+        T::contains_point(self, point)
+    }
+    fn signed_distance(&self, point: Point<f32>) -> f32 {
+        T::signed_distance(self, point)
+    }
+    fn project_point(&self, point: Point<f32>) -> PointProjection {
+        T::project_point(self, point)
+    }
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Circle {
     pub center: Point<f32>,
     pub radius: f32,
@@ -66,6 +208,42 @@ impl Circle {
     pub fn new(center: Point<f32>, radius: f32) -> Self {
         Circle { center, radius }
     }
+
+    /// Inscribed polygon that stays within `tolerance` of the circle.
+    ///
+    /// The polygon has at most 1024 vertices, which is also what tolerances too small to reach,
+    /// zero, negative and NaN ones get.
+    #[cfg(feature = "alloc")]
+    pub fn to_polygon(&self, tolerance: f32) -> ConvexPolygon {
+        let max = MAX_CIRCLE_VERTICES as f32;
+        let ratio = 1. - tolerance / self.radius;
+        let count = if ratio < 1. {
+            Real::ceil(PI / Real::acos(ratio.max(-1.))).min(max)
+        } else {
+            max
+        };
+        let count = count.max(3.) as usize;
+        let points = (0..count)
+            .map(|i| {
+                let angle = i as f32 * 2. * PI / count as f32;
+                self.center + Vector::new(Real::cos(angle), Real::sin(angle)) * self.radius
+            })
+            .collect();
+        ConvexPolygon::new(points)
+    }
+
+    pub fn area(&self) -> f32 {
+        PI * self.radius * self.radius
+    }
+
+    pub fn centroid(&self) -> Point<f32> {
+        self.center
+    }
+
+    /// Moment of inertia around the centroid.
+    pub fn inertia(&self, density: f32) -> f32 {
+        density * self.area() * self.radius * self.radius / 2.
+    }
 }
 
 impl Shape for Circle {
@@ -73,11 +251,36 @@ impl Shape for Circle {
         self.center.coords
     }
     fn farthest_in_dir(&self, dir: Vector<f32>) -> Vector<f32> {
-        self.center.coords + dir.normalize() * self.radius
+        match unit(dir) {
+            Some(dir) => self.center.coords + dir * self.radius,
+            None => self.center.coords,
+        }
+    }
+    fn contains_point(&self, point: Point<f32>) -> bool {
+        (point - self.center).norm_squared() <= self.radius * self.radius
+    }
+    fn signed_distance(&self, point: Point<f32>) -> f32 {
+        (point - self.center).norm() - self.radius
+    }
+    fn project_point(&self, point: Point<f32>) -> PointProjection {
+        let diff = point - self.center;
+        let dir = if diff == zero() {
+            Vector::new(1., 0.)
+        } else {
+            diff.normalize()
+        };
+        let inside = diff.norm_squared() <= self.radius * self.radius;
+        PointProjection::new(
+            point,
+            self.center + dir * self.radius,
+            inside,
+            Feature::Boundary,
+        )
     }
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Aabb {
     pub from: Point<f32>,
     pub to: Point<f32>,
@@ -89,6 +292,53 @@ impl Aabb {
     pub fn new(from: Point<f32>, to: Point<f32>) -> Self {
         Aabb { from, to }
     }
+
+    /// Corners counter-clockwise starting from `from`.
+    pub fn corners(&self) -> [Point<f32>; 4] {
+        [
+            self.from,
+            Point::new(self.to.x, self.from.y),
+            self.to,
+            Point::new(self.from.x, self.to.y),
+        ]
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn to_polygon(&self) -> ConvexPolygon {
+        ConvexPolygon::new(self.corners().to_vec())
+    }
+
+    /// Smallest box containing the shape.
+    pub fn bounding<S>(shape: &S) -> Self
+    where
+        S: Shape + ?Sized,
+    {
+        Aabb::new(
+            Point::new(
+                shape.farthest_in_dir(Vector::new(-1., 0.)).x,
+                shape.farthest_in_dir(Vector::new(0., -1.)).y,
+            ),
+            Point::new(
+                shape.farthest_in_dir(Vector::new(1., 0.)).x,
+                shape.farthest_in_dir(Vector::new(0., 1.)).y,
+            ),
+        )
+    }
+
+    pub fn area(&self) -> f32 {
+        let size = self.to - self.from;
+        Real::abs(size.x * size.y)
+    }
+
+    pub fn centroid(&self) -> Point<f32> {
+        self.from + (self.to - self.from) / 2.
+    }
+
+    /// Moment of inertia around the centroid.
+    pub fn inertia(&self, density: f32) -> f32 {
+        let size = self.to - self.from;
+        density * self.area() * size.norm_squared() / 12.
+    }
 }
 
 impl Shape for Aabb {
@@ -101,13 +351,62 @@ impl Shape for Aabb {
             if dir.y > 0. { self.to.y } else { self.from.y },
         )
     }
+    fn contains_point(&self, point: Point<f32>) -> bool {
+        self.from.x <= point.x
+            && point.x <= self.to.x
+            && self.from.y <= point.y
+            && point.y <= self.to.y
+    }
+    fn signed_distance(&self, point: Point<f32>) -> f32 {
+        let half = (self.to - self.from) / 2.;
+        let diff = (point - (self.from + half)).abs() - half;
+        let outside = Vector::new(diff.x.max(0.), diff.y.max(0.)).norm();
+        outside + diff.x.max(diff.y).min(0.)
+    }
+    fn project_point(&self, point: Point<f32>) -> PointProjection {
+        if self.contains_point(point) {
+            // Vertices and edges go counter-clockwise starting from `from`.
+            let sides = [
+                (point.y - self.from.y, Point::new(point.x, self.from.y)),
+                (self.to.x - point.x, Point::new(self.to.x, point.y)),
+                (self.to.y - point.y, Point::new(point.x, self.to.y)),
+                (point.x - self.from.x, Point::new(self.from.x, point.y)),
+            ];
+            let (edge, &(_, closest)) = sides
+                .iter()
+                .enumerate()
+                .min_by(|(_, (d1, _)), (_, (d2, _))| d1.total_cmp(d2))
+                .unwrap();
+            return PointProjection::new(point, closest, true, Feature::Edge(edge));
+        }
+        let closest = Point::new(
+            point.x.max(self.from.x).min(self.to.x),
+            point.y.max(self.from.y).min(self.to.y),
+        );
+        let right = closest.x != point.x && closest.x == self.to.x;
+        let top = closest.y != point.y && closest.y == self.to.y;
+        let feature = match (closest.x != point.x, closest.y != point.y) {
+            (true, true) => Feature::Vertex(match (right, top) {
+                (false, false) => 0,
+                (true, false) => 1,
+                (true, true) => 2,
+                (false, true) => 3,
+            }),
+            (true, false) => Feature::Edge(if right { 1 } else { 3 }),
+            _ => Feature::Edge(if top { 2 } else { 0 }),
+        };
+        PointProjection::new(point, closest, false, feature)
+    }
 }
 
+#[cfg(feature = "alloc")]
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ConvexPolygon {
     pub points: Vec<Point<f32>>,
 }
 
+#[cfg(feature = "alloc")]
 impl ConvexPolygon {
     pub fn new(points: Vec<Point<f32>>) -> Self {
         ConvexPolygon { points }
@@ -124,61 +423,487 @@ impl ConvexPolygon {
         ConvexPolygon::new(vec![from, to])
     }
 
-    fn dot(&self, index: isize, dir: Vector<f32>) -> f32 {
-        if index == -1 {
-            self.points[self.points.len() - 1]
-        } else if index as usize == self.points.len() {
-            self.points[0]
-        } else {
-            self.points[index as usize]
-        }
-        .coords
-        .dot(&dir)
+    pub fn area(&self) -> f32 {
+        Real::abs(self.signed_area())
+    }
+
+    /// Degenerate polygons like line segments get the average of their points.
+    pub fn centroid(&self) -> Point<f32> {
+        centroid(&self.points)
+    }
+
+    /// Moment of inertia around the centroid.
+    pub fn inertia(&self, density: f32) -> f32 {
+        inertia(&self.points, density)
+    }
+
+    pub(crate) fn signed_area(&self) -> f32 {
+        signed_area(&self.points)
+    }
+
+    /// Index of the point farthest in `dir`, searching from the `hint` index.
+    ///
+    /// Passing the index returned by the previous query makes slowly changing directions cheap.
+    pub fn farthest_index_from(&self, dir: Vector<f32>, hint: usize) -> usize {
+        climb(&self.points, (hint % self.points.len()) as isize, dir)
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Shape for ConvexPolygon {
     fn start(&self) -> Vector<f32> {
         self.points[0].coords
     }
     fn farthest_in_dir(&self, dir: Vector<f32>) -> Vector<f32> {
-        let size = self.points.len() as isize;
-
-        let mut index = 0;
-        let mut cur = self.dot(index, dir);
-        // Negative cur means that were looking from opposite direction
-        if cur < 0. {
-            index = size / 2;
-            cur = self.dot(index, dir);
+        self.points[farthest_index(&self.points, dir)].coords
+    }
+    fn project_point(&self, point: Point<f32>) -> PointProjection {
+        project_on_polygon(&self.points, point)
+    }
+}
+
+/// Convex polygon with a fixed number of points, for when there's no heap to put a `ConvexPolygon` in.
+#[derive(Clone, Debug)]
+pub struct FixedPolygon<const N: usize> {
+    pub points: [Point<f32>; N],
+}
+
+impl<const N: usize> FixedPolygon<N> {
+    /// Fails to compile for polygons without points.
+    const NOT_EMPTY: () = assert!(N > 0, "FixedPolygon needs at least one point");
+
+    pub fn new(points: [Point<f32>; N]) -> Self {
+        let () = Self::NOT_EMPTY;
+        FixedPolygon { points }
+    }
+
+    pub fn area(&self) -> f32 {
+        Real::abs(signed_area(&self.points))
+    }
+
+    /// Degenerate polygons like line segments get the average of their points.
+    pub fn centroid(&self) -> Point<f32> {
+        centroid(&self.points)
+    }
+
+    /// Moment of inertia around the centroid.
+    pub fn inertia(&self, density: f32) -> f32 {
+        inertia(&self.points, density)
+    }
+
+    /// Index of the point farthest in `dir`, searching from the `hint` index.
+    pub fn farthest_index_from(&self, dir: Vector<f32>, hint: usize) -> usize {
+        let () = Self::NOT_EMPTY;
+        climb(&self.points, (hint % N) as isize, dir)
+    }
+}
+
+impl<const N: usize> Shape for FixedPolygon<N> {
+    fn start(&self) -> Vector<f32> {
+        let () = Self::NOT_EMPTY;
+        self.points[0].coords
+    }
+    fn farthest_in_dir(&self, dir: Vector<f32>) -> Vector<f32> {
+        let () = Self::NOT_EMPTY;
+        self.points[farthest_index(&self.points, dir)].coords
+    }
+    fn project_point(&self, point: Point<f32>) -> PointProjection {
+        let () = Self::NOT_EMPTY;
+        project_on_polygon(&self.points, point)
+    }
+}
+
+fn edges(points: &[Point<f32>]) -> impl Iterator<Item = (&Point<f32>, &Point<f32>)> {
+    points
+        .iter()
+        .zip(points.iter().skip(1).chain(points.first()))
+}
+
+fn signed_area(points: &[Point<f32>]) -> f32 {
+    edges(points)
+        .map(|(a, b)| cross(a.coords, b.coords))
+        .sum::<f32>()
+        / 2.
+}
+
+fn centroid(points: &[Point<f32>]) -> Point<f32> {
+    let area = signed_area(points);
+    if area == 0. {
+        let sum = points.iter().fold(Vector::zeros(), |sum, p| sum + p.coords);
+        return Point::from(sum / points.len() as f32);
+    }
+    let sum = edges(points).fold(Vector::zeros(), |sum, (a, b)| {
+        sum + (a.coords + b.coords) * cross(a.coords, b.coords)
+    });
+    Point::from(sum / (6. * area))
+}
+
+fn inertia(points: &[Point<f32>], density: f32) -> f32 {
+    let area = signed_area(points);
+    if area == 0. {
+        return 0.;
+    }
+    let c = centroid(points);
+    let sum: f32 = edges(points)
+        .map(|(a, b)| {
+            let (a, b) = (a - c, b - c);
+            cross(a, b) * (a.dot(&a) + a.dot(&b) + b.dot(&b))
+        })
+        .sum();
+    density * sum / 12. * Real::signum(area)
+}
+
+fn farthest_index(points: &[Point<f32>], dir: Vector<f32>) -> usize {
+    let size = points.len() as isize;
+
+    let mut index = 0;
+    // Negative cur means that were looking from opposite direction
+    if dot(points, index, dir) < 0. {
+        index = size / 2;
+    }
+    climb(points, index, dir)
+}
+
+/// Climbs from `index`, which has to be one of the points, to the point farthest in `dir`.
+fn climb(points: &[Point<f32>], mut index: isize, dir: Vector<f32>) -> usize {
+    let size = points.len() as isize;
+    let mut cur = dot(points, index, dir);
+    let (left_index, left) = past_plateau(points, index, cur, -1, dir);
+    let (right_index, right) = past_plateau(points, index, cur, 1, dir);
+    if left <= cur && cur >= right {
+        return index as usize;
+    }
+
+    let step = if left > right {
+        index = left_index;
+        cur = left;
+        -1
+    } else {
+        index = right_index;
+        cur = right;
+        1
+    };
+    // A lap is enough for any convex polygon, and keeps NaN coordinates from climbing forever.
+    for _ in 0..size {
+        let (next_index, next) = past_plateau(points, index, cur, step, dir);
+        if cur >= next {
+            return index as usize;
         }
+        index = next_index;
+        cur = next;
+    }
+    index as usize
+}
 
-        let left = self.dot(index - 1, dir);
-        let right = self.dot(index + 1, dir);
-        if left <= cur && cur >= right {
-            return self.points[index as usize].coords;
+/// First point after `index` in the `step` direction that isn't as far in `dir` as `index`,
+/// which is `cur` far.
+///
+/// Duplicate points and edges perpendicular to `dir` are stepped over like this, so climbing sees
+/// which way the polygon goes after them. Returns `index` itself if every point is as far.
+fn past_plateau(
+    points: &[Point<f32>],
+    index: isize,
+    cur: f32,
+    step: isize,
+    dir: Vector<f32>,
+) -> (isize, f32) {
+    let size = points.len() as isize;
+    let mut next = index;
+    for _ in 1..size {
+        next += step;
+        if next == -1 {
+            next = size - 1;
+        } else if next == size {
+            next = 0;
+        }
+        let dot = points[next as usize].coords.dot(&dir);
+        if dot != cur {
+            return (next, dot);
         }
+    }
+    (index, cur)
+}
 
-        let step = if left > right {
-            cur = left;
-            -1
+fn dot(points: &[Point<f32>], index: isize, dir: Vector<f32>) -> f32 {
+    if index == -1 {
+        points[points.len() - 1]
+    } else if index as usize == points.len() {
+        points[0]
+    } else {
+        points[index as usize]
+    }
+    .coords
+    .dot(&dir)
+}
+
+fn project_on_polygon(points: &[Point<f32>], point: Point<f32>) -> PointProjection {
+    if points.len() == 1 {
+        return points[0].project_point(point);
+    }
+    let orientation = signed_area(points);
+    let mut inside = orientation != 0.;
+    let mut closest = (f32::MAX, point, Feature::Unknown);
+    for (i, (a, b)) in edges(points).enumerate() {
+        let edge = b - a;
+        if cross(edge, point - a) * orientation < 0. {
+            inside = false;
+        }
+        let t = ((point - a).dot(&edge) / edge.norm_squared()).clamp(0., 1.);
+        let candidate = a + edge * t;
+        let distance = (point - candidate).norm_squared();
+        if distance < closest.0 {
+            let feature = if t == 0. {
+                Feature::Vertex(i)
+            } else if t == 1. {
+                Feature::Vertex((i + 1) % points.len())
+            } else {
+                Feature::Edge(i)
+            };
+            closest = (distance, candidate, feature);
+        }
+    }
+    let inside = inside || closest.0 == 0.;
+    PointProjection::new(point, closest.1, inside, closest.2)
+}
+
+/// Convex polygon with precomputed edge normals, so that support queries take O(log n) time.
+///
+/// Worth it over `ConvexPolygon` only for hulls with many points. Only the points are serialized.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(from = "ConvexPolygon", into = "ConvexPolygon")
+)]
+pub struct ConvexHull {
+    points: Vec<Point<f32>>,
+    /// Angle of the normal of the edge starting at each point, never decreasing from the first one.
+    angles: Vec<f32>,
+}
+
+#[cfg(feature = "alloc")]
+impl ConvexHull {
+    /// Points can be wound either way. Repeated points are removed.
+    pub fn new(mut points: Vec<Point<f32>>) -> Self {
+        points.dedup();
+        while points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        if signed_area(&points) < 0. {
+            points.reverse();
+        }
+        // Convex polygons only turn left, so collinear points and slightly concave rounding
+        // give equal angles instead of ones wrapped a full turn out of order.
+        let mut angles: Vec<f32> = Vec::with_capacity(points.len());
+        for (from, to) in edges(&points) {
+            let edge = to - from;
+            let angle = Real::atan2(edge.y, edge.x) - PI / 2.;
+            angles.push(match angles.last() {
+                Some(&prev) => prev + wrap_from(-PI / 2., angle - prev).max(0.),
+                None => angle,
+            });
+        }
+        ConvexHull { points, angles }
+    }
+
+    pub fn points(&self) -> &[Point<f32>] {
+        &self.points
+    }
+
+    /// Index of the point farthest in `dir`.
+    pub fn farthest_index(&self, dir: Vector<f32>) -> usize {
+        if self.points.len() < 2 {
+            return 0;
+        }
+        let angle = wrap_from(self.angles[0], Real::atan2(dir.y, dir.x));
+        // Point `i` is the farthest for directions between the normals of edges `i - 1` and `i`.
+        self.angles.partition_point(|&a| a < angle) % self.points.len()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<ConvexPolygon> for ConvexHull {
+    fn from(polygon: ConvexPolygon) -> Self {
+        ConvexHull::new(polygon.points)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<ConvexHull> for ConvexPolygon {
+    fn from(hull: ConvexHull) -> Self {
+        ConvexPolygon::new(hull.points)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Shape for ConvexHull {
+    fn start(&self) -> Vector<f32> {
+        self.points[0].coords
+    }
+    fn farthest_in_dir(&self, dir: Vector<f32>) -> Vector<f32> {
+        self.points[self.farthest_index(dir)].coords
+    }
+}
+
+/// Every built-in shape, for storing them without boxing and matching on them exhaustively.
+///
+/// This is also how `Box<dyn Shape>` gets serialized, tagged with the name of the variant.
+/// Points are stored as `[x, y]` everywhere, including the position of a `Point`.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(tag = "type"))]
+pub enum ShapeKind {
+    Circle(Circle),
+    Aabb(Aabb),
+    ConvexPolygon(ConvexPolygon),
+    ConvexHull(ConvexHull),
+    #[cfg_attr(feature = "serde", serde(with = "position"))]
+    Point(Point<f32>),
+}
+
+#[cfg(feature = "alloc")]
+impl ShapeKind {
+    /// Copies a boxed built-in shape, or returns `None` for other shapes.
+    pub fn from_dyn(shape: &dyn Shape) -> Option<Self> {
+        if let Some(circle) = shape.downcast_ref::<Circle>() {
+            Some(ShapeKind::Circle(circle.clone()))
+        } else if let Some(aabb) = shape.downcast_ref::<Aabb>() {
+            Some(ShapeKind::Aabb(aabb.clone()))
+        } else if let Some(polygon) = shape.downcast_ref::<ConvexPolygon>() {
+            Some(ShapeKind::ConvexPolygon(polygon.clone()))
+        } else if let Some(hull) = shape.downcast_ref::<ConvexHull>() {
+            Some(ShapeKind::ConvexHull(hull.clone()))
+        } else if let Some(point) = shape.downcast_ref::<Point<f32>>() {
+            Some(ShapeKind::Point(*point))
         } else {
-            cur = right;
-            1
-        };
-        index += step;
-        loop {
-            if index == -1 {
-                index = size - 1;
-            }
-            if index == size {
-                index = 0;
-            }
-            let next = self.dot(index + step, dir);
-            if cur >= next {
-                return self.points[index as usize].coords;
-            }
-            cur = next;
-            index += step;
+            shape.downcast_ref::<ShapeKind>().cloned()
         }
     }
 }
+
+#[cfg(feature = "alloc")]
+macro_rules! dispatch {
+    ($kind:expr, $shape:ident => $body:expr) => {
+        match $kind {
+            ShapeKind::Circle($shape) => $body,
+            ShapeKind::Aabb($shape) => $body,
+            ShapeKind::ConvexPolygon($shape) => $body,
+            ShapeKind::ConvexHull($shape) => $body,
+            ShapeKind::Point($shape) => $body,
+        }
+    };
+}
+
+#[cfg(feature = "alloc")]
+impl Shape for ShapeKind {
+    fn start(&self) -> Vector<f32> {
+        dispatch!(self, shape => shape.start())
+    }
+    fn farthest_in_dir(&self, dir: Vector<f32>) -> Vector<f32> {
+        dispatch!(self, shape => shape.farthest_in_dir(dir))
+    }
+    fn contains_point(&self, point: Point<f32>) -> bool {
+        dispatch!(self, shape => shape.contains_point(point))
+    }
+    fn signed_distance(&self, point: Point<f32>) -> f32 {
+        dispatch!(self, shape => shape.signed_distance(point))
+    }
+    fn project_point(&self, point: Point<f32>) -> PointProjection {
+        dispatch!(self, shape => shape.project_point(point))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<Circle> for ShapeKind {
+    fn from(circle: Circle) -> Self {
+        ShapeKind::Circle(circle)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<Aabb> for ShapeKind {
+    fn from(aabb: Aabb) -> Self {
+        ShapeKind::Aabb(aabb)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<ConvexPolygon> for ShapeKind {
+    fn from(polygon: ConvexPolygon) -> Self {
+        ShapeKind::ConvexPolygon(polygon)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<ConvexHull> for ShapeKind {
+    fn from(hull: ConvexHull) -> Self {
+        ShapeKind::ConvexHull(hull)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> From<FixedPolygon<N>> for ShapeKind {
+    fn from(polygon: FixedPolygon<N>) -> Self {
+        ShapeKind::ConvexPolygon(ConvexPolygon::new(polygon.points.to_vec()))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<Point<f32>> for ShapeKind {
+    fn from(point: Point<f32>) -> Self {
+        ShapeKind::Point(point)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<ShapeKind> for Box<dyn Shape> {
+    fn from(shape: ShapeKind) -> Self {
+        dispatch!(shape, shape => Box::new(shape))
+    }
+}
+
+/// Fails for shapes that aren't built in.
+#[cfg(feature = "serde")]
+impl Serialize for Box<dyn Shape> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ShapeKind::from_dyn(&**self)
+            .ok_or_else(|| ser::Error::custom("can only serialize built-in shapes"))?
+            .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Box<dyn Shape> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ShapeKind::deserialize(deserializer).map(Into::into)
+    }
+}
+
+/// Points are stored in a `point` field, as tagged variants can't hold bare sequences.
+#[cfg(feature = "serde")]
+mod position {
+    use crate::Point;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct Position {
+        point: Point<f32>,
+    }
+
+    pub fn serialize<S: Serializer>(point: &Point<f32>, serializer: S) -> Result<S::Ok, S::Error> {
+        Position { point: *point }.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Point<f32>, D::Error> {
+        Position::deserialize(deserializer).map(|position| position.point)
+    }
+}
+
+/// Wraps `angle` to be within a full turn starting from `from`.
+#[cfg(feature = "alloc")]
+fn wrap_from(from: f32, angle: f32) -> f32 {
+    let turn = 2. * PI;
+    let diff = angle - from;
+    from + diff - turn * Real::floor(diff / turn)
+}
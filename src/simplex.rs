@@ -1,4 +1,8 @@
-use crate::Vector;
+use crate::{cross, Vector};
+
+use nalgebra::Scalar;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 #[derive(Copy, Clone)]
 pub enum Winding {
@@ -7,18 +11,18 @@ pub enum Winding {
 }
 
 #[derive(Debug, Clone)]
-pub enum Simplex {
-    Point(Vector<f32>),
-    Line(Vector<f32>, Vector<f32>),
-    Triangle(Vector<f32>, Vector<f32>, Vector<f32>),
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Simplex<N: Scalar = f32> {
+    Point(Vector<N>),
+    Line(Vector<N>, Vector<N>),
+    Triangle(Vector<N>, Vector<N>, Vector<N>),
 }
 
 impl Simplex {
     pub fn winding(&self) -> Winding {
         use self::{Simplex::*, Winding::*};
-        if let Triangle(v1, v2, _) = self {
-            let dot = v1.dot(&v2);
-            if dot < 0. {
+        if let Triangle(v1, v2, v3) = self {
+            if cross(v2 - v1, v3 - v1) < 0. {
                 Right
             } else {
                 Left
@@ -27,8 +31,10 @@ impl Simplex {
             panic!("Winding is only defined for the triangle.");
         }
     }
+}
 
-    pub fn add(&mut self, p: Vector<f32>) {
+impl<N: Scalar> Simplex<N> {
+    pub fn add(&mut self, p: Vector<N>) {
         use self::Simplex::*;
         *self = match self {
             Point(p2) => Line(*p2, p),
@@ -37,17 +43,26 @@ impl Simplex {
         }
     }
 
-    pub fn last(&self) -> &Vector<f32> {
+    pub fn last(&self) -> &Vector<N> {
         use self::Simplex::*;
         match self {
             Point(p) | Line(_, p) | Triangle(_, _, p) => p,
         }
     }
+
+    pub(crate) fn map<M: Scalar>(&self, f: impl Fn(Vector<N>) -> Vector<M>) -> Simplex<M> {
+        use self::Simplex::*;
+        match *self {
+            Point(a) => Point(f(a)),
+            Line(a, b) => Line(f(a), f(b)),
+            Triangle(a, b, c) => Triangle(f(a), f(b), f(c)),
+        }
+    }
 }
 
-impl<'a> IntoIterator for &'a Simplex {
-    type IntoIter = SimplexIter<'a>;
-    type Item = Vector<f32>;
+impl<'a, N: Scalar> IntoIterator for &'a Simplex<N> {
+    type IntoIter = SimplexIter<'a, N>;
+    type Item = Vector<N>;
     fn into_iter(self) -> Self::IntoIter {
         SimplexIter {
             simplex: self,
@@ -56,13 +71,13 @@ impl<'a> IntoIterator for &'a Simplex {
     }
 }
 
-pub struct SimplexIter<'a> {
-    simplex: &'a Simplex,
+pub struct SimplexIter<'a, N: Scalar = f32> {
+    simplex: &'a Simplex<N>,
     cur: u8,
 }
 
-impl Iterator for SimplexIter<'_> {
-    type Item = Vector<f32>;
+impl<N: Scalar> Iterator for SimplexIter<'_, N> {
+    type Item = Vector<N>;
     fn next(&mut self) -> Option<Self::Item> {
         use self::Simplex::*;
         match self.simplex {
@@ -0,0 +1,106 @@
+use crate::simplex::Simplex;
+use crate::Vector;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Step taken by GJK or EPA, reported to a `Trace` as the query runs.
+#[derive(Clone, Debug)]
+pub enum Event {
+    /// Direction that the next support point is searched in.
+    Direction(Vector<f32>),
+    /// Point of the Minkowski difference found by the support function.
+    Support(Vector<f32>),
+    /// GJK simplex after a point was added or it was reduced.
+    Simplex(Simplex),
+    /// EPA polytope after a point was added.
+    #[cfg(feature = "alloc")]
+    Polytope(Vec<Vector<f32>>),
+    /// Number of points in the EPA polytope, recorded alongside `Polytope` but even when disabled.
+    PolytopeSize(usize),
+    /// Edge of the EPA polytope closest to the origin, with its outward normal.
+    ClosestEdge {
+        from: Vector<f32>,
+        to: Vector<f32>,
+        normal: Vector<f32>,
+        distance: f32,
+    },
+}
+
+/// Why GJK or EPA stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Termination {
+    /// GJK found a direction that separates the shapes.
+    Separated,
+    /// GJK enclosed the origin in its simplex.
+    Enclosed,
+    /// The origin is on the boundary of a simplex or polytope with no area, so the shapes just touch.
+    Touching,
+    /// EPA found an edge that's within tolerance of the boundary.
+    Converged,
+    /// The query gave up, which usually means NaN or huge coordinates.
+    IterationLimit,
+}
+
+/// Receives the steps of a query, for debugging failing cases.
+pub trait Trace {
+    fn record(&mut self, event: Event);
+
+    /// Called once at the end of the query with the number of iterations its main loop ran.
+    fn finish(&mut self, _termination: Termination, _iterations: usize) {}
+
+    /// Events that need to allocate are only built when this is true.
+    fn enabled(&self) -> bool {
+        true
+    }
+}
+
+/// Ignores every event, which is what the untraced queries use.
+impl Trace for () {
+    fn record(&mut self, _event: Event) {}
+    fn enabled(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Trace for Vec<Event> {
+    fn record(&mut self, event: Event) {
+        self.push(event);
+    }
+}
+
+/// Counters of a query, for finding the shape pairs that take unusually long.
+///
+/// Passing the same `Stats` to GJK and then EPA adds up the work of both.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub iterations: usize,
+    pub support_calls: usize,
+    /// Number of points in the last GJK simplex.
+    pub simplex_size: usize,
+    /// Number of points in the last EPA polytope.
+    pub polytope_size: usize,
+    /// How the last query ended, `None` until one has.
+    pub termination: Option<Termination>,
+}
+
+impl Trace for Stats {
+    fn record(&mut self, event: Event) {
+        match event {
+            Event::Support(_) => self.support_calls += 1,
+            Event::Simplex(simplex) => self.simplex_size = simplex.into_iter().count(),
+            Event::PolytopeSize(size) => self.polytope_size = size,
+            _ => {}
+        }
+    }
+
+    fn enabled(&self) -> bool {
+        false
+    }
+
+    fn finish(&mut self, termination: Termination, iterations: usize) {
+        self.iterations += iterations;
+        self.termination = Some(termination);
+    }
+}
@@ -0,0 +1,60 @@
+use kolli_desu::batch::{aabbs_aabb, circles_aabb, circles_circle, collides_many};
+use kolli_desu::gjk::collides;
+use kolli_desu::shapes::{Aabb, Circle};
+use kolli_desu::Point;
+
+/// Poses on a grid around the origin, with a count that doesn't divide into full lanes.
+fn poses() -> Vec<Point<f32>> {
+    let mut poses = vec![];
+    for x in -10..=10 {
+        for y in -10..=10 {
+            poses.push(Point::new(x as f32 * 0.2, y as f32 * 0.2 + 0.05));
+        }
+    }
+    poses
+}
+
+#[test]
+fn circle_batches_agree_with_gjk() {
+    let poses = poses();
+    let circles: Vec<_> = (0..poses.len())
+        .map(|i| Circle::new(Point::new(0., 0.), 0.1 + (i % 5) as f32 * 0.05))
+        .collect();
+    let circle = Circle::new(Point::new(0.2, 0.), 0.5);
+    let aabb = Aabb::new(Point::new(-0.5, -0.3), Point::new(0.7, 0.4));
+    let pos = Point::new(0.1, -0.1);
+
+    let expected: Vec<_> = circles
+        .iter()
+        .zip(&poses)
+        .map(|(c, p)| collides((c, *p), (&circle, pos)))
+        .collect();
+    assert_eq!(circles_circle(&circles, &poses, (&circle, pos)), expected);
+
+    let expected: Vec<_> = circles
+        .iter()
+        .zip(&poses)
+        .map(|(c, p)| collides((c, *p), (&aabb, pos)))
+        .collect();
+    assert_eq!(circles_aabb(&circles, &poses, (&aabb, pos)), expected);
+}
+
+#[test]
+fn aabb_batch_agrees_with_gjk() {
+    let poses = poses();
+    let aabbs: Vec<_> = (0..poses.len())
+        .map(|i| {
+            Aabb::new(
+                Point::new(0., 0.),
+                Point::new(0.15, 0.1 + (i % 3) as f32 * 0.1),
+            )
+        })
+        .collect();
+    let aabb = Aabb::new(Point::new(-0.5, -0.3), Point::new(0.7, 0.4));
+    let pos = Point::new(0.1, -0.1);
+
+    let shapes: Vec<_> = aabbs.iter().zip(&poses).map(|(a, p)| (a, *p)).collect();
+    let expected = collides_many(&shapes, (&aabb, pos));
+    assert!(expected.contains(&true) && expected.contains(&false));
+    assert_eq!(aabbs_aabb(&aabbs, &poses, (&aabb, pos)), expected);
+}
@@ -0,0 +1,68 @@
+use kolli_desu::cast::cast;
+use kolli_desu::shapes::{Aabb, Circle};
+use kolli_desu::{Point, Vector};
+
+#[test]
+fn circle_cast_hits_aabb() {
+    let circle = Circle::new(Point::new(0., 0.), 0.5);
+    let wall = Aabb::new(Point::new(2., -1.), Point::new(3., 1.));
+    let hit = cast(
+        (&circle, Point::new(0., 0.)),
+        Vector::new(4., 0.),
+        (&wall, Point::new(0., 0.)),
+    )
+    .expect("Cast should hit the wall");
+    assert!(
+        (hit.toi - 0.375).abs() < 0.001,
+        "Time of impact wasn't close enough to 0.375: {}",
+        hit.toi
+    );
+    assert!(
+        (hit.normal - Vector::new(-1., 0.)).norm() < 0.01,
+        "Normal wasn't close enough to (-1, 0): {}",
+        hit.normal
+    );
+}
+
+#[test]
+fn circle_cast_misses_aabb() {
+    let circle = Circle::new(Point::new(0., 0.), 0.5);
+    let wall = Aabb::new(Point::new(2., -1.), Point::new(3., 1.));
+    let hit = cast(
+        (&circle, Point::new(0., 0.)),
+        Vector::new(1., 0.),
+        (&wall, Point::new(0., 0.)),
+    );
+    assert!(hit.is_none(), "Cast shouldn't reach the wall: {:?}", hit);
+}
+
+#[test]
+fn fast_cast_doesnt_tunnel() {
+    let circle = Circle::new(Point::new(0., 0.), 0.1);
+    let wall = Aabb::new(Point::new(5., -1.), Point::new(5.1, 1.));
+    let hit = cast(
+        (&circle, Point::new(0., 0.)),
+        Vector::new(100., 0.),
+        (&wall, Point::new(0., 0.)),
+    )
+    .expect("Cast should hit the thin wall");
+    assert!(
+        (hit.toi - 0.049).abs() < 0.001,
+        "Time of impact wasn't close enough to 0.049: {}",
+        hit.toi
+    );
+}
+
+#[test]
+fn initial_contact_only_blocks_motion_into_it() {
+    let aabb = Aabb::new(Point::new(-0.5, -0.5), Point::new(0.5, 0.5));
+    let ground = Aabb::new(Point::new(-10., -1.5), Point::new(10., -0.5));
+    let origin = Point::new(0., 0.);
+    for &motion in &[Vector::new(1., 0.), Vector::new(-1., 0.5)] {
+        let hit = cast((&aabb, origin), motion, (&ground, origin));
+        assert!(hit.is_none(), "{} shouldn't hit: {:?}", motion, hit);
+    }
+    let hit = cast((&aabb, origin), Vector::new(1., -1.), (&ground, origin))
+        .expect("Cast into the ground should hit it");
+    assert_eq!(hit.toi, 0.);
+}
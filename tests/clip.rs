@@ -0,0 +1,90 @@
+use kolli_desu::clip::{clip, intersection, overlap_area};
+use kolli_desu::shapes::{Aabb, Circle, ConvexPolygon};
+use kolli_desu::Point;
+
+use std::f32::consts::PI;
+
+fn assert_close(value: f32, correct: f32, tolerance: f32) {
+    assert!(
+        (value - correct).abs() < tolerance,
+        "{} wasn't close enough to {}",
+        value,
+        correct
+    );
+}
+
+#[test]
+fn clip_overlapping_squares() {
+    let a = Aabb::new(Point::new(0., 0.), Point::new(2., 2.)).to_polygon();
+    let mut b = Aabb::new(Point::new(1., 1.), Point::new(3., 3.)).to_polygon();
+    assert_close(clip(&a, &b).area(), 1., 0.0001);
+    b.points.reverse();
+    let result = clip(&a, &b);
+    assert_close(result.area(), 1., 0.0001);
+    assert!((result.centroid() - Point::new(1.5, 1.5)).norm() < 0.0001);
+}
+
+#[test]
+fn clip_disjoint_is_empty() {
+    let a = Aabb::new(Point::new(0., 0.), Point::new(1., 1.)).to_polygon();
+    let b = ConvexPolygon::new(vec![
+        Point::new(2., 0.),
+        Point::new(3., 0.),
+        Point::new(2., 1.),
+    ]);
+    assert!(clip(&a, &b).points.is_empty());
+}
+
+#[test]
+fn circle_inside_aabb_overlap() {
+    let circle = Circle::new(Point::new(0., 0.), 1.);
+    let aabb = Aabb::new(Point::new(-1., -1.), Point::new(1., 1.));
+    let area = overlap_area(
+        (&circle, Point::new(5., 5.)),
+        (&aabb, Point::new(5., 5.)),
+        0.001,
+    )
+    .unwrap();
+    assert_close(area, PI, 0.01);
+}
+
+#[test]
+fn half_circle_overlap() {
+    let circle = Circle::new(Point::new(0., 0.), 1.);
+    let aabb = Aabb::new(Point::new(0., -2.), Point::new(2., 2.));
+    let polygon = intersection(
+        (&circle, Point::new(0., 0.)),
+        (&aabb, Point::new(0., 0.)),
+        0.0001,
+    )
+    .unwrap();
+    assert_close(polygon.area(), PI / 2., 0.01);
+    assert!(polygon.points.iter().all(|p| p.x >= -0.0001));
+}
+
+#[test]
+fn unsupported_shape_has_no_intersection() {
+    let aabb = Aabb::new(Point::new(-1., -1.), Point::new(1., 1.));
+    let point = Point::new(0., 0.);
+    assert!(intersection((&aabb, point), (&point, point), 0.01).is_none());
+}
+
+#[test]
+fn tiny_tolerances_are_capped() {
+    let circle = Circle::new(Point::new(0., 0.), 1.);
+    for &tolerance in &[1e-3, 1e-8, 0., -1., f32::NAN] {
+        let polygon = circle.to_polygon(tolerance);
+        assert!(polygon.points.len() >= 3 && polygon.points.len() <= 1024);
+        assert_close(polygon.area(), PI, 0.01);
+    }
+    assert_eq!(circle.to_polygon(0.).points.len(), 1024);
+    assert_eq!(circle.to_polygon(10.).points.len(), 3);
+    let aabb = Aabb::new(Point::new(0., -2.), Point::new(2., 2.));
+    let area = overlap_area(
+        (&circle, Point::new(0., 0.)),
+        (&aabb, Point::new(0., 0.)),
+        0.,
+    )
+    .unwrap();
+    assert_close(area, PI / 2., 0.01);
+}
@@ -0,0 +1,159 @@
+use kolli_desu::controller::CharacterController;
+use kolli_desu::shapes::{Aabb, Circle, ConvexPolygon, Shape};
+use kolli_desu::{Point, Vector};
+
+fn origin() -> Point<f32> {
+    Point::new(0., 0.)
+}
+
+/// Slope rising to the right, just below a circle of radius 0.5 at the origin.
+fn slope(angle: f32) -> ConvexPolygon {
+    let normal = Vector::new(-angle.sin(), angle.cos());
+    let along = Vector::new(angle.cos(), angle.sin());
+    let on_surface = Point::from(normal * -0.51);
+    let from = on_surface - along * 10.;
+    let to = on_surface + along * 10.;
+    ConvexPolygon::new(vec![from, Point::new(to.x, from.y), to])
+}
+
+#[test]
+fn falls_onto_ground() {
+    let controller = CharacterController::default();
+    let character = Circle::new(origin(), 0.5);
+    let ground = Aabb::new(Point::new(-10., -2.), Point::new(10., -1.));
+    let movement = controller.move_and_slide(
+        (&character, origin()),
+        Vector::new(0., -2.),
+        &[(&ground, origin())],
+    );
+    assert!(movement.grounded, "Character should be on the ground");
+    assert!(
+        (movement.motion.y + 0.5).abs() < 0.01,
+        "Character should stop on the ground: {}",
+        movement.motion
+    );
+}
+
+#[test]
+fn walks_when_spawned_exactly_on_ground() {
+    let controller = CharacterController::default();
+    let ground = Aabb::new(Point::new(-10., -1.5), Point::new(10., -0.5));
+    let characters: [Box<dyn Shape>; 2] = [
+        Box::new(Aabb::new(Point::new(-0.5, -0.5), Point::new(0.5, 0.5))),
+        Box::new(Circle::new(origin(), 0.5)),
+    ];
+    for character in &characters {
+        let movement = controller.move_and_slide(
+            (&**character, origin()),
+            Vector::new(1., 0.),
+            &[(&ground, origin())],
+        );
+        assert!(
+            (movement.motion.x - 1.).abs() < 0.01 && movement.motion.y >= 0.,
+            "Character should walk along the ground: {:?}",
+            movement
+        );
+        assert!(movement.grounded, "Character should stay on the ground");
+    }
+}
+
+#[test]
+fn slides_along_wall() {
+    let controller = CharacterController::default();
+    let character = Circle::new(origin(), 0.5);
+    let wall = Aabb::new(Point::new(1., -10.), Point::new(2., 10.));
+    let movement = controller.move_and_slide(
+        (&character, origin()),
+        Vector::new(1., 1.),
+        &[(&wall, origin())],
+    );
+    assert!(!movement.grounded, "Wall isn't ground");
+    assert!(
+        movement.motion.x < 0.5 + 0.01,
+        "Character went through the wall: {}",
+        movement.motion
+    );
+    assert!(
+        (movement.motion.y - 1.).abs() < 0.01,
+        "Character should keep moving up along the wall: {}",
+        movement.motion
+    );
+}
+
+#[test]
+fn climbs_gentle_slope_but_not_steep_one() {
+    let controller = CharacterController::default();
+    let character = Circle::new(origin(), 0.5);
+    let gentle = slope(20f32.to_radians());
+    let steep = slope(70f32.to_radians());
+    let obstacles: &[(&dyn Shape, Point<f32>)] = &[(&gentle, origin())];
+    let movement =
+        controller.move_and_slide((&character, origin()), Vector::new(1., -0.1), obstacles);
+    assert!(movement.grounded, "Gentle slope should be ground");
+    assert!(
+        movement.motion.y > 0.,
+        "Character should climb the slope: {}",
+        movement.motion
+    );
+
+    let obstacles: &[(&dyn Shape, Point<f32>)] = &[(&steep, origin())];
+    let movement =
+        controller.move_and_slide((&character, origin()), Vector::new(1., -0.1), obstacles);
+    assert!(!movement.grounded, "Steep slope shouldn't be ground");
+    assert!(
+        movement.motion.y <= 0.,
+        "Character shouldn't climb the steep slope: {}",
+        movement.motion
+    );
+}
+
+#[test]
+fn snaps_to_ground() {
+    let controller = CharacterController::default();
+    let character = Circle::new(origin(), 0.5);
+    let ground = Aabb::new(Point::new(-10., -2.), Point::new(10., -0.55));
+    let movement = controller.move_and_slide(
+        (&character, origin()),
+        Vector::new(1., 0.),
+        &[(&ground, origin())],
+    );
+    assert!(movement.grounded, "Character should snap to the ground");
+    assert!(
+        (movement.motion.y + 0.05).abs() < 0.01,
+        "Character should be pulled down: {}",
+        movement.motion
+    );
+}
+
+#[test]
+fn depenetrates_after_spawning_inside() {
+    let controller = CharacterController::default();
+    let character = Circle::new(origin(), 0.5);
+    let ground = Aabb::new(Point::new(-10., -2.), Point::new(10., -0.2));
+    let movement = controller.move_and_slide(
+        (&character, origin()),
+        Vector::new(0., 0.),
+        &[(&ground, origin())],
+    );
+    assert!(
+        (movement.motion.y - 0.3).abs() < 0.01,
+        "Character should be pushed out of the ground: {}",
+        movement.motion
+    );
+}
+
+#[test]
+fn nan_obstacle_does_not_panic() {
+    let controller = CharacterController::default();
+    let character = Circle::new(origin(), 0.5);
+    let ground = Aabb::new(Point::new(-10., -2.), Point::new(10., -1.));
+    controller.move_and_slide(
+        (&character, origin()),
+        Vector::new(0., -2.),
+        &[
+            (&ground, origin()),
+            (&ground, Point::new(f32::NAN, 0.)),
+            (&ground, origin()),
+        ],
+    );
+}
@@ -0,0 +1,118 @@
+use kolli_desu::cast::cast;
+use kolli_desu::debug_draw::{draw_cast_hit, draw_contact, draw_shape, Color, DebugRenderer};
+use kolli_desu::dispatch::contact;
+use kolli_desu::shapes::{Aabb, Circle, ConvexHull, ConvexPolygon, Shape, ShapeKind};
+use kolli_desu::{Point, Vector};
+
+const RED: Color = [255, 0, 0, 255];
+
+#[derive(Debug, PartialEq)]
+enum Call {
+    Line(Point<f32>, Point<f32>),
+    Circle(Point<f32>, f32),
+    Polygon(Vec<Point<f32>>),
+    Point(Point<f32>),
+    Text(String),
+}
+
+#[derive(Default)]
+struct Recorder(Vec<Call>);
+
+impl DebugRenderer for Recorder {
+    fn line(&mut self, from: Point<f32>, to: Point<f32>, _color: Color) {
+        self.0.push(Call::Line(from, to));
+    }
+    fn circle(&mut self, center: Point<f32>, radius: f32, _color: Color) {
+        self.0.push(Call::Circle(center, radius));
+    }
+    fn polygon(&mut self, points: &[Point<f32>], _color: Color) {
+        self.0.push(Call::Polygon(points.to_vec()));
+    }
+    fn point(&mut self, point: Point<f32>, _color: Color) {
+        self.0.push(Call::Point(point));
+    }
+    fn text(&mut self, _at: Point<f32>, text: &str, _color: Color) {
+        self.0.push(Call::Text(text.to_owned()));
+    }
+}
+
+#[test]
+fn built_in_shapes_are_drawn_exactly() {
+    let pos = Point::new(1., 2.);
+    let circle = Circle::new(Point::new(0.5, 0.), 2.);
+    let aabb = Aabb::new(Point::new(0., 0.), Point::new(1., 1.));
+    let segment = ConvexPolygon::new_line_segment(Point::new(0., 0.), Point::new(1., 0.));
+    let kind = ShapeKind::Circle(circle.clone());
+    let shapes: [&dyn Shape; 5] = [&circle, &aabb, &segment, &kind, &Point::new(1., 1.)];
+    let mut recorder = Recorder::default();
+    for &shape in &shapes {
+        draw_shape(&mut recorder, (shape, pos), RED);
+    }
+    assert_eq!(
+        recorder.0,
+        vec![
+            Call::Circle(Point::new(1.5, 2.), 2.),
+            Call::Polygon(vec![
+                Point::new(1., 2.),
+                Point::new(2., 2.),
+                Point::new(2., 3.),
+                Point::new(1., 3.),
+            ]),
+            Call::Line(Point::new(1., 2.), Point::new(2., 2.)),
+            Call::Circle(Point::new(1.5, 2.), 2.),
+            Call::Point(Point::new(2., 3.)),
+        ]
+    );
+}
+
+#[test]
+fn other_shapes_are_outlined_from_support_points() {
+    let hull = ConvexHull::new(vec![
+        Point::new(0., 0.),
+        Point::new(1., 0.),
+        Point::new(1., 1.),
+        Point::new(0., 1.),
+    ]);
+    let mut recorder = Recorder::default();
+    draw_shape(&mut recorder, (&hull, Point::new(0., 0.)), RED);
+    match &recorder.0[..] {
+        [Call::Polygon(points)] => {
+            assert_eq!(points.len(), 4);
+            for corner in hull.points() {
+                assert!(points.contains(corner));
+            }
+        }
+        calls => panic!("{:?}", calls),
+    }
+}
+
+#[test]
+fn contacts_and_cast_hits() {
+    let circle = Circle::new(Point::new(0., 0.), 1.);
+    let contact = contact(
+        (&circle, Point::new(0., 0.)),
+        (&circle, Point::new(1.5, 0.)),
+    )
+    .unwrap();
+    let mut recorder = Recorder::default();
+    draw_contact(&mut recorder, &contact, RED);
+    assert_eq!(
+        recorder.0[0],
+        Call::Line(Point::new(0.5, 0.), Point::new(1., 0.))
+    );
+    assert_eq!(recorder.0[1], Call::Point(Point::new(0.75, 0.)));
+
+    let motion = Vector::new(4., 0.);
+    let a = (&circle as &dyn Shape, Point::new(0., 0.));
+    let hit = cast(a, motion, (&circle, Point::new(3., 0.))).unwrap();
+    let mut recorder = Recorder::default();
+    draw_cast_hit(&mut recorder, a, motion, &hit, RED);
+    match &recorder.0[..] {
+        [Call::Line(from, to), Call::Circle(center, _), Call::Line(..), Call::Text(_)] => {
+            assert_eq!(*from, Point::new(0., 0.));
+            assert!((to.x - 1.).abs() < 0.01);
+            assert_eq!(center, to);
+        }
+        calls => panic!("{:?}", calls),
+    }
+}
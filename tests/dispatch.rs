@@ -0,0 +1,123 @@
+use nalgebra::Isometry2;
+
+use kolli_desu::dispatch::{
+    collides, collides_kinds, contact, contact_kinds, penetration, penetration_kinds,
+};
+use kolli_desu::epa::solve;
+use kolli_desu::gjk::{self, collides_internal};
+use kolli_desu::shapes::{Aabb, Circle, ConvexPolygon, Shape, ShapeKind};
+use kolli_desu::{Point, Vector};
+
+const TAU: f32 = 2. * ::std::f32::consts::PI;
+
+#[test]
+fn fast_paths_agree_with_gjk() {
+    let circle = Circle::new(Point::new(0.1, 0.), 0.4);
+    let aabb = Aabb::new(Point::new(-0.5, -0.25), Point::new(0.5, 0.25));
+    let polygon = ConvexPolygon::new_rectangle(Point::new(0., 0.), Point::new(0.5, 0.5), 0.3);
+    let shapes: [&dyn Shape; 3] = [&circle, &aabb, &polygon];
+    let steps = 72;
+    for &a in &shapes {
+        for &b in &shapes {
+            for distance in &[0.3, 0.6, 0.9, 1.2] {
+                for n in 0..steps {
+                    let pos = Isometry2::new(Vector::new(0., 0.), n as f32 * TAU / steps as f32)
+                        * Point::new(*distance, 0.);
+                    let a = (a, Point::new(0., 0.));
+                    let b = (b, pos);
+                    let (gjk_collides, simplex) = collides_internal(a, b);
+                    assert_eq!(collides(a, b), gjk_collides, "{}", pos);
+                    assert_eq!(gjk::collides(a, b), gjk_collides, "{}", pos);
+                    if !gjk_collides {
+                        assert!(penetration(a, b).is_none(), "{}", pos);
+                        continue;
+                    }
+                    let (_, depth) = solve(a, b, simplex);
+                    let (_, fast_depth) = penetration(a, b).unwrap();
+                    assert!(
+                        (depth - fast_depth).abs() < 0.01,
+                        "Depth {} wasn't close enough to {} at {}",
+                        fast_depth,
+                        depth,
+                        pos
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn circle_circle_contact() {
+    let circle = Circle::new(Point::new(0., 0.), 1.);
+    let contact = contact(
+        (&circle, Point::new(0., 0.)),
+        (&circle, Point::new(0., 1.5)),
+    )
+    .unwrap();
+    assert!((contact.normal - Vector::new(0., 1.)).norm() < 0.0001);
+    assert!((contact.depth - 0.5).abs() < 0.0001);
+    assert!((contact.point - Point::new(0., 0.75)).norm() < 0.0001);
+}
+
+#[test]
+fn aabb_aabb_contact() {
+    let aabb = Aabb::new(Point::new(0., 0.), Point::new(2., 1.));
+    let result = contact((&aabb, Point::new(0., 0.)), (&aabb, Point::new(-1.5, 0.25))).unwrap();
+    assert_eq!(result.normal, Vector::new(-1., 0.));
+    assert!((result.depth - 0.5).abs() < 0.0001);
+    assert!((result.point - Point::new(0.25, 0.625)).norm() < 0.0001);
+    assert!(contact((&aabb, Point::new(0., 0.)), (&aabb, Point::new(0., 2.))).is_none());
+}
+
+#[test]
+fn aabb_with_circle_inside() {
+    let aabb = Aabb::new(Point::new(-1., -1.), Point::new(1., 1.));
+    let circle = Circle::new(Point::new(0., 0.), 0.25);
+    let (normal, depth) =
+        penetration((&aabb, Point::new(0., 0.)), (&circle, Point::new(0.5, 0.))).unwrap();
+    assert_eq!(normal, Vector::new(1., 0.));
+    assert!((depth - 0.75).abs() < 0.0001);
+    let (normal, _) =
+        penetration((&circle, Point::new(0.5, 0.)), (&aabb, Point::new(0., 0.))).unwrap();
+    assert_eq!(normal, Vector::new(-1., 0.));
+}
+
+#[test]
+fn kinds_agree_with_trait_objects() {
+    let kinds: [ShapeKind; 4] = [
+        Circle::new(Point::new(0.1, 0.), 0.4).into(),
+        Aabb::new(Point::new(-0.5, -0.25), Point::new(0.5, 0.25)).into(),
+        ConvexPolygon::new_rectangle(Point::new(0., 0.), Point::new(0.5, 0.5), 0.3).into(),
+        Point::new(0.2, 0.1).into(),
+    ];
+    let boxed: Vec<Box<dyn Shape>> = kinds.iter().cloned().map(Into::into).collect();
+    for (ka, a) in kinds.iter().zip(&boxed) {
+        for (kb, b) in kinds.iter().zip(&boxed) {
+            for n in 0..8 {
+                let pos = Point::new(n as f32 * 0.15, 0.1);
+                let (ka, kb) = ((ka, Point::new(0., 0.)), (kb, pos));
+                let (a, b) = ((&**a, Point::new(0., 0.)), (&**b, pos));
+                assert_eq!(collides_kinds(ka, kb), collides(a, b));
+                assert_eq!(penetration_kinds(ka, kb), penetration(a, b));
+                assert_eq!(
+                    contact_kinds(ka, kb).map(|contact| contact.point),
+                    contact(a, b).map(|contact| contact.point)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn unbounded_aabb_does_not_panic() {
+    let everything = Aabb::new(
+        Point::new(f32::NEG_INFINITY, f32::NEG_INFINITY),
+        Point::new(f32::INFINITY, f32::INFINITY),
+    );
+    let circle = Circle::new(Point::new(0., 0.), 1.);
+    penetration(
+        (&everything, Point::new(0., 0.)),
+        (&circle, Point::new(f32::INFINITY, 0.)),
+    );
+}
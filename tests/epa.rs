@@ -1,11 +1,10 @@
-use kolli_desu::Perp;
-use std::fmt::Debug;
-
 use nalgebra::Isometry2;
 
-use kolli_desu::epa::solve;
-use kolli_desu::gjk::collides_internal;
+use kolli_desu::epa::{solve, solve_internal, solve_traced};
+use kolli_desu::gjk::{collides_internal, collides_traced};
 use kolli_desu::shapes::{Aabb, Circle, ConvexPolygon, Shape};
+use kolli_desu::simplex::Simplex;
+use kolli_desu::trace::{Event, Stats, Termination};
 use kolli_desu::{Point, Vector};
 
 const TAU: f32 = 2. * ::std::f32::consts::PI;
@@ -84,3 +83,214 @@ fn small_circle_penetrates_bigger_one() {
         );
     }
 }
+
+#[test]
+fn trace_records_every_step() {
+    let a = (
+        &Aabb::new(Point::new(0., 0.), Point::new(1., 1.)),
+        Point::new(0., 0.),
+    );
+    let b = (&Circle::new(Point::new(1., 0.5), 0.3), Point::new(0., 0.));
+    let mut events = vec![];
+    let (collides, simplex) = collides_traced(a, b, &mut events);
+    assert!(collides);
+    match events.last() {
+        Some(Event::Simplex(last)) => {
+            assert_eq!(
+                last.into_iter().collect::<Vec<_>>(),
+                (&simplex).into_iter().collect::<Vec<_>>()
+            )
+        }
+        event => panic!("GJK should end with its simplex, not {:?}", event),
+    }
+    let supports = events
+        .iter()
+        .filter(|e| matches!(e, Event::Support(_)))
+        .count();
+    assert!(supports >= (&simplex).into_iter().count());
+
+    let mut events = vec![];
+    let (normal, depth, polytope) = solve_traced(a, b, simplex.clone(), &mut events);
+    assert_eq!(
+        (normal, depth, polytope.clone()),
+        solve_internal(a, b, simplex)
+    );
+    let edge = events.iter().rev().find_map(|e| match e {
+        Event::ClosestEdge { normal, .. } => Some(*normal),
+        _ => None,
+    });
+    assert_eq!(edge, Some(normal));
+    let last_polytope = events.iter().rev().find_map(|e| match e {
+        Event::Polytope(polytope) => Some(polytope.clone()),
+        _ => None,
+    });
+    assert_eq!(last_polytope, Some(polytope));
+}
+
+#[test]
+fn stats_count_the_work() {
+    let a = (
+        &Aabb::new(Point::new(0., 0.), Point::new(1., 1.)),
+        Point::new(0., 0.),
+    );
+    let b = (&Circle::new(Point::new(1., 0.5), 0.3), Point::new(0., 0.));
+    let mut stats = Stats::default();
+    let (_, simplex) = collides_traced(a, b, &mut stats);
+    assert_eq!(stats.termination, Some(Termination::Enclosed));
+    assert_eq!(stats.simplex_size, 3);
+    assert_eq!(stats.support_calls, stats.iterations + 1);
+
+    let mut stats = Stats::default();
+    let mut events = vec![];
+    let (_, _, polytope) = solve_traced(a, b, simplex.clone(), &mut stats);
+    solve_traced(a, b, simplex, &mut events);
+    let edges = events
+        .iter()
+        .filter(|e| matches!(e, Event::ClosestEdge { .. }))
+        .count();
+    assert_eq!(stats.termination, Some(Termination::Converged));
+    assert_eq!(stats.iterations, edges);
+    assert_eq!(stats.support_calls, edges);
+    assert_eq!(stats.polytope_size, polytope.len());
+
+    let far = (b.0, Point::new(3., 0.));
+    let mut stats = Stats::default();
+    collides_traced(a, far, &mut stats);
+    assert_eq!(stats.termination, Some(Termination::Separated));
+}
+
+fn assert_unit(normal: Vector<f32>) {
+    assert!(
+        (normal.norm() - 1.).abs() < 0.0001,
+        "{} isn't unit length",
+        normal
+    );
+}
+
+#[test]
+fn touching_boxes() {
+    let aabb = Aabb::new(Point::new(0., 0.), Point::new(1., 1.));
+    let a = (&aabb, Point::new(0., 0.));
+    for &(pos, normal) in &[
+        (Point::new(1., 0.), Vector::new(1., 0.)),
+        (Point::new(0., -1.), Vector::new(0., -1.)),
+        (Point::new(-1., 0.5), Vector::new(-1., 0.)),
+    ] {
+        let b = (&aabb, pos);
+        let (collides, simplex) = collides_internal(a, b);
+        assert!(collides, "{}", pos);
+        let (vector, depth) = solve(a, b, simplex);
+        assert!(depth.abs() < 0.00001, "{}: depth {}", pos, depth);
+        assert!((vector - normal).norm() < 0.0001, "{}: {}", pos, vector);
+    }
+}
+
+#[test]
+fn concentric_circles() {
+    for &radius in &[0.01, 1., 100.] {
+        let a = (&Circle::new(Point::new(0., 0.), radius), Point::new(3., 4.));
+        let b = (
+            &Circle::new(Point::new(0., 0.), radius / 2.),
+            Point::new(3., 4.),
+        );
+        let (_, simplex) = collides_internal(a, b);
+        let mut stats = Stats::default();
+        let (normal, depth, _) = solve_traced(a, b, simplex, &mut stats);
+        let correct = radius * 1.5;
+        assert!(
+            (depth - correct).abs() < 0.0001 * correct,
+            "Depth wasn't close enough to {}: {}",
+            correct,
+            depth
+        );
+        assert_unit(normal);
+        assert_eq!(stats.termination, Some(Termination::Converged));
+        // Every direction is as deep, so the whole circle has to be refined.
+        assert!(stats.iterations <= 512, "{:?}", stats);
+    }
+}
+
+#[test]
+fn shallow_penetrations_converge_quickly() {
+    let circle = Circle::new(Point::new(0., 0.), 1.);
+    let aabb = Aabb::new(Point::new(-1., -1.), Point::new(1., 1.));
+    for &pos in &[Point::new(0., 0.), Point::new(300., 400.)] {
+        for &depth in &[0.00001, 0.001] {
+            let a = (&circle, pos);
+            let dir = Vector::new(0.6, 0.8);
+            let pairs: [(&dyn Shape, Point<f32>); 2] = [
+                (&circle, pos + dir * (2. - depth)),
+                (&aabb, pos + Vector::new(2. - depth, 0.3)),
+            ];
+            for &b in &pairs {
+                let (_, simplex) = collides_internal(a, b);
+                let mut stats = Stats::default();
+                solve_traced(a, b, simplex, &mut stats);
+                assert_eq!(stats.termination, Some(Termination::Converged));
+                assert!(stats.iterations <= 32, "{:?}", stats);
+            }
+        }
+    }
+}
+
+/// Simplices of support points that GJK doesn't end with, but EPA should still accept.
+#[test]
+fn flat_and_point_simplices() {
+    // The Minkowski difference is a circle of radius 2 around (-1, 0).
+    let a = (&Circle::new(Point::new(0., 0.), 1.), Point::new(0., 0.));
+    let b = (&Circle::new(Point::new(0., 0.), 1.), Point::new(1., 0.));
+    let (_, simplex) = collides_internal(a, b);
+    let (_, correct) = solve(a, b, simplex);
+    for simplex in &[
+        Simplex::Point(Vector::new(1., 0.)),
+        Simplex::Point(Vector::new(-1., 2.)),
+        Simplex::Line(Vector::new(1., 0.), Vector::new(-3., 0.)),
+        Simplex::Line(Vector::new(-1., -2.), Vector::new(-1., -2.)),
+        Simplex::Triangle(
+            Vector::new(1., 0.),
+            Vector::new(-3., 0.),
+            Vector::new(1., 0.),
+        ),
+    ] {
+        let (normal, depth) = solve(a, b, simplex.clone());
+        assert!(
+            (depth - correct).abs() < 0.0001,
+            "{:?}: depth {} instead of {}",
+            simplex,
+            depth,
+            correct
+        );
+        assert!((normal - Vector::new(1., 0.)).norm() < 0.01, "{}", normal);
+    }
+}
+
+#[test]
+fn collinear_segments_only_touch() {
+    let a = (
+        &ConvexPolygon::new_line_segment(Point::new(0., 0.), Point::new(2., 0.)),
+        Point::new(0., 0.),
+    );
+    let b = (
+        &ConvexPolygon::new_line_segment(Point::new(1., 0.), Point::new(3., 0.)),
+        Point::new(0., 0.),
+    );
+    let (collides, simplex) = collides_internal(a, b);
+    assert!(collides);
+    let (normal, depth) = solve(a, b, simplex);
+    assert_eq!(depth, 0.);
+    assert!(normal.x.abs() < 0.0001, "{}", normal);
+    assert_unit(normal);
+}
+
+#[test]
+fn tiny_shapes_have_unit_normals() {
+    let a = (
+        &Circle::new(Point::new(0., 1e-39), 1e-40),
+        Point::new(0., 0.),
+    );
+    let b = (&Circle::new(Point::new(0., 0.), 1e-39), Point::new(0., 0.));
+    let (collides, simplex) = collides_internal(a, b);
+    assert!(collides);
+    let (normal, _) = solve(a, b, simplex);
+    assert_unit(normal);
+}
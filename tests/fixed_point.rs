@@ -0,0 +1,152 @@
+#![cfg(feature = "fixed")]
+
+use kolli_desu::fixed_point::{self, Aabb, Circle, ConvexPolygon, Point, Scalar, Shape, Vector};
+use kolli_desu::{epa, gjk, shapes, Perp};
+
+use std::cell::Cell;
+
+fn point(x: f32, y: f32) -> Point {
+    Point::new(Scalar::from_num(x), Scalar::from_num(y))
+}
+
+const OFFSETS: [(f32, f32); 3] = [(0.75, 0.3), (-1.2, 0.9), (0.1, -2.1)];
+
+fn fixed_shapes() -> Vec<Box<dyn Shape>> {
+    vec![
+        Box::new(Circle::new(point(0., 0.), Scalar::from_num(1.5))),
+        Box::new(Aabb::new(point(-1., -0.5), point(1., 0.5))),
+        Box::new(ConvexPolygon::new(vec![
+            point(0., 0.),
+            point(2., 0.25),
+            point(0.5, 1.75),
+        ])),
+    ]
+}
+
+fn float_shapes() -> Vec<Box<dyn shapes::Shape>> {
+    use kolli_desu::Point;
+    vec![
+        Box::new(shapes::Circle::new(Point::new(0., 0.), 1.5)),
+        Box::new(shapes::Aabb::new(
+            Point::new(-1., -0.5),
+            Point::new(1., 0.5),
+        )),
+        Box::new(shapes::ConvexPolygon::new(vec![
+            Point::new(0., 0.),
+            Point::new(2., 0.25),
+            Point::new(0.5, 1.75),
+        ])),
+    ]
+}
+
+/// Normal and depth bits of every shape against every shape at every offset.
+#[rustfmt::skip]
+const RECORDED: [Option<(i64, i64, i64)>; 27] = [
+    Some((3992651935, 1582869102, 9415552182)),
+    Some((-3428051691, 2587509551, 6442481161)),
+    Some((191127970, -4290712549, 3855293150)),
+    Some((-195, 4294967296, 7301444693)),
+    Some((-1932405699, 3835694499, 4521692002)),
+    None,
+    Some((3994629760, 1577871084, 2973117280)),
+    Some((-532725128, 4261801029, 1967559941)),
+    Some((3705873785, -2171000590, 3459076667)),
+    Some((-195, 4294967296, 7301444693)),
+    Some((-1932405638, 3835694529, 4521692001)),
+    None,
+    Some((0, 4294967296, 3006477056)),
+    Some((0, 4294967296, 429496832)),
+    None,
+    Some((0, 4294967296, 858993408)),
+    None,
+    Some((0, -4294967296, 644245504)),
+    Some((532725118, -4261801031, 7321447466)),
+    Some((-4129713900, 1179918293, 424867693)),
+    None,
+    Some((0, -4294967296, 3435973888)),
+    None,
+    None,
+    Some((3037000500, 3037000500, 3644400563)),
+    Some((-4129713909, 1179918259, 1946864960)),
+    None,
+];
+
+#[test]
+fn recorded_queries_are_bit_identical() {
+    let shapes = fixed_shapes();
+    let mut recorded = RECORDED.iter();
+    for a in &shapes {
+        for b in &shapes {
+            for &(x, y) in &OFFSETS {
+                let result = fixed_point::penetration((&**a, point(0., 0.)), (&**b, point(x, y)));
+                let bits = result.map(|(normal, depth)| {
+                    (normal.x.to_bits(), normal.y.to_bits(), depth.to_bits())
+                });
+                assert_eq!(bits, *recorded.next().unwrap());
+            }
+        }
+    }
+}
+
+#[test]
+fn depths_match_floating_point() {
+    use kolli_desu::Point;
+    let fixed = fixed_shapes();
+    let float = float_shapes();
+    for (fa, a) in fixed.iter().zip(&float) {
+        for (fb, b) in fixed.iter().zip(&float) {
+            for &(x, y) in &OFFSETS {
+                let result = fixed_point::penetration((&**fa, point(0., 0.)), (&**fb, point(x, y)));
+                let a = (&**a, Point::origin());
+                let b = (&**b, Point::new(x, y));
+                let (collides, simplex) = gjk::collides_internal(a, b);
+                assert_eq!(result.is_some(), collides);
+                if let Some((_, depth)) = result {
+                    let (_, correct) = epa::solve(a, b, simplex);
+                    assert!((depth.to_num::<f32>() - correct).abs() < 0.01);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn aabb_starts_from_its_center() {
+    let aabb = Aabb::new(point(-1., -0.5), point(3., 0.5));
+    assert_eq!(aabb.start(), point(1., 0.).coords);
+}
+
+/// Inconsistent support function that keeps stepping sideways, so GJK never finishes.
+struct Spiral {
+    last: Cell<Vector>,
+}
+
+impl Shape for Spiral {
+    fn start(&self) -> Vector {
+        Vector::new(Scalar::ONE, Scalar::ZERO)
+    }
+    fn farthest_in_dir(&self, dir: Vector) -> Vector {
+        let size = |v: Vector| v.x.abs().max(v.y.abs());
+        let dir = dir / size(dir);
+        let mut side = dir.perpendicular();
+        if side.dot(&self.last.get()) < 0 {
+            side = -side;
+        }
+        let point = dir * Scalar::from_num(0.00001)
+            + side * (size(self.last.get()) * Scalar::from_num(1.1));
+        self.last.set(point);
+        point
+    }
+}
+
+#[test]
+fn iteration_limit_is_not_a_collision() {
+    let spiral = Spiral {
+        last: Cell::new(point(1., 1.).coords),
+    };
+    let origin = point(0., 0.);
+    assert!(!fixed_point::collides(
+        (&spiral, origin),
+        (&Circle::new(origin, Scalar::ZERO), origin)
+    ));
+}
@@ -2,18 +2,22 @@
 // use kolli_desu::Perp;
 // use kolli_desu::{line_line_intersection_point, LineIntersectError};
 use kolli_desu::Perp;
+use std::cell::Cell;
 use std::fmt::Debug;
 
 use nalgebra::Isometry2;
 
-use kolli_desu::gjk::collides;
+use kolli_desu::gjk::{
+    closest_point_with, collides, collides_internal, collides_seeded, collides_traced,
+};
 use kolli_desu::shapes::{Aabb, Circle, ConvexPolygon, Shape};
+use kolli_desu::trace::{Stats, Termination};
 use kolli_desu::{Point, Vector};
 
 const TAU: f32 = 2. * ::std::f32::consts::PI;
 
 fn zero() -> Point<f32> {
-    Point::from_coordinates(::nalgebra::zero())
+    Point::from(::nalgebra::zero::<Vector<f32>>())
 }
 
 fn assert_collides<S1, S2>(hitbox1: &S1, hitbox2: &S2)
@@ -637,3 +641,141 @@ fn rectangle_rectangle_corner_cross_collides() {
     assert_collides(&rectangle1, &rectangle4);
     assert_collides(&rectangle1, &rectangle5);
 }
+
+/// Counts how many times the support function is queried.
+struct Counted<S> {
+    shape: S,
+    calls: Cell<usize>,
+}
+
+impl<S: Shape> Shape for Counted<S> {
+    fn start(&self) -> Vector<f32> {
+        self.shape.start()
+    }
+    fn farthest_in_dir(&self, dir: Vector<f32>) -> Vector<f32> {
+        self.calls.set(self.calls.get() + 1);
+        self.shape.farthest_in_dir(dir)
+    }
+}
+
+#[test]
+fn seeded_agrees_with_cold_start() {
+    let circle = Circle::new(Point::new(0., 0.), 0.5);
+    let rectangle = ConvexPolygon::new_rectangle(Point::new(-1., 0.), Point::new(1., 0.), 0.5);
+    let mut seed = Vector::new(1., 0.);
+    let steps = 200;
+    for n in 0..steps {
+        let pos = Point::new(-2. + 4. * n as f32 / steps as f32, 0.6);
+        let (cold, _) = collides_internal((&circle, pos), (&rectangle, zero()));
+        let (warm, _) = collides_seeded((&circle, pos), (&rectangle, zero()), &mut seed);
+        assert_eq!(cold, warm, "{}", pos);
+    }
+}
+
+#[test]
+fn seeded_converges_quickly() {
+    let moving = Counted {
+        shape: ConvexPolygon::new_rectangle(Point::new(-1., 0.), Point::new(1., 0.), 0.5),
+        calls: Cell::new(0),
+    };
+    let other = Aabb::new(Point::new(-1., -1.), Point::new(1., 0.));
+    let mut seed = Vector::new(1., 0.);
+    collides_seeded((&moving, Point::new(0.3, 3.)), (&other, zero()), &mut seed);
+    for n in 0..100 {
+        let pos = Point::new(0.3 + n as f32 * 0.001, 3. - n as f32 * 0.01);
+        moving.calls.set(0);
+        let (collides, _) = collides_seeded((&moving, pos), (&other, zero()), &mut seed);
+        assert!(!collides);
+        assert!(
+            moving.calls.get() <= 2,
+            "Seeded query took {} support calls",
+            moving.calls.get()
+        );
+    }
+}
+
+#[test]
+fn zero_seed_with_collinear_separated_shapes() {
+    let point = Point::new(0., 0.);
+    let segment = ConvexPolygon::new(vec![Point::new(-10., 0.), Point::new(-5., 0.)]);
+    let mut seed = Vector::zeros();
+    let (collides, _) =
+        collides_seeded((&point, zero()), (&segment, Point::new(15., 0.)), &mut seed);
+    assert!(!collides);
+}
+
+#[test]
+fn nan_positions_terminate() {
+    let polygon = ConvexPolygon::new_rectangle(Point::new(-1., 0.), Point::new(1., 0.), 0.5);
+    let circle = Circle::new(Point::new(0., 0.), 1.);
+    let pos = Point::new(f32::NAN, 0.);
+    assert!(!collides((&polygon, pos), (&circle, zero())));
+    assert!(!collides((&circle, zero()), (&polygon, pos)));
+}
+
+/// Inconsistent support function that keeps stepping sideways, so GJK never finishes.
+struct Spiral {
+    last: Cell<Vector<f32>>,
+}
+
+impl Shape for Spiral {
+    fn start(&self) -> Vector<f32> {
+        Vector::new(1., 0.)
+    }
+    fn farthest_in_dir(&self, dir: Vector<f32>) -> Vector<f32> {
+        let dir = dir.normalize();
+        let mut side = Vector::new(-dir.y, dir.x);
+        if side.dot(&self.last.get()) < 0. {
+            side = -side;
+        }
+        let point = dir * 0.00001 + side * self.last.get().norm() * 1.1;
+        self.last.set(point);
+        point
+    }
+}
+
+#[test]
+fn iteration_limit_is_not_a_collision() {
+    let spiral = Spiral {
+        last: Cell::new(Vector::new(1., 1.)),
+    };
+    let mut stats = Stats::default();
+    let (collides, _) = collides_traced((&spiral, zero()), (&zero(), zero()), &mut stats);
+    assert_eq!(stats.termination, Some(Termination::IterationLimit));
+    assert!(!collides);
+}
+
+#[test]
+fn nan_support_in_closest_point() {
+    let support = |dir: Vector<f32>| {
+        if dir.x > 0. {
+            Vector::new(1., 1.)
+        } else if dir.y < 0. {
+            Vector::new(1., -1.)
+        } else {
+            Vector::new(f32::NAN, 0.)
+        }
+    };
+    closest_point_with(Vector::new(-1., 0.), support);
+}
+
+#[test]
+fn large_shapes_near_an_edge() {
+    // Found by fuzzing, the origin is much closer to the last edge than the polygon is big.
+    let polygon = ConvexPolygon::new(vec![
+        Point::new(3156.3125, 49.32845),
+        Point::new(-1409.5436, 2873.4204),
+        Point::new(-1508.2684, 2821.9507),
+        Point::new(-1714.8369, -2600.51),
+    ]);
+    let normal = Vector::new(0.47785777, -0.8784372);
+    let distance = polygon.points[0].coords.dot(&normal);
+    for &offset in &[0.01, 0.1, 1.] {
+        let outside = Point::from(normal * (distance + offset));
+        assert!(!collides((&polygon, zero()), (&outside, zero())));
+        assert!(!collides((&outside, zero()), (&polygon, zero())));
+        let inside = Point::from(normal * (distance - offset));
+        assert!(collides((&polygon, zero()), (&inside, zero())));
+        assert!(collides((&inside, zero()), (&polygon, zero())));
+    }
+}
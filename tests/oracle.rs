@@ -0,0 +1,172 @@
+//! Differential tests of GJK and EPA against a brute force separating axis oracle.
+
+use proptest::prelude::*;
+use proptest::test_runner::FileFailurePersistence;
+
+use kolli_desu::epa::solve;
+use kolli_desu::gjk::{collides, collides_internal};
+use kolli_desu::shapes::{Aabb, Circle, ConvexPolygon, ShapeKind};
+use kolli_desu::{Point, Vector};
+
+const TAU: f32 = 2. * ::std::f32::consts::PI;
+
+/// Results closer than this to touching are left out, since either answer is right there.
+const MARGIN: f32 = 0.001;
+
+/// Allowed difference between the depth from EPA and the oracle.
+const DEPTH_TOLERANCE: f32 = 0.001;
+
+fn circle() -> impl Strategy<Value = ShapeKind> {
+    (-0.5f32..0.5, -0.5f32..0.5, 0.1f32..2.)
+        .prop_map(|(x, y, radius)| Circle::new(Point::new(x, y), radius).into())
+}
+
+fn aabb() -> impl Strategy<Value = ShapeKind> {
+    (-0.5f32..0.5, -0.5f32..0.5, 0.1f32..3., 0.1f32..3.).prop_map(|(x, y, width, height)| {
+        let half = Vector::new(width, height) / 2.;
+        Aabb::new(Point::new(x, y) - half, Point::new(x, y) + half).into()
+    })
+}
+
+/// Points on a circle are always in convex position, so sorted angles make a convex polygon.
+fn polygon() -> impl Strategy<Value = ShapeKind> {
+    (
+        -0.5f32..0.5,
+        -0.5f32..0.5,
+        0.1f32..2.,
+        prop::collection::vec(0f32..TAU, 3..8),
+    )
+        .prop_filter_map(
+            "needs three distinct corners",
+            |(x, y, radius, mut angles)| {
+                angles.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                angles.dedup_by(|a, b| *a - *b < 0.05);
+                if angles.len() < 3 {
+                    return None;
+                }
+                let points = angles
+                    .iter()
+                    .map(|angle| Point::new(x + radius * angle.cos(), y + radius * angle.sin()))
+                    .collect();
+                Some(ConvexPolygon::new(points).into())
+            },
+        )
+}
+
+fn shape() -> impl Strategy<Value = ShapeKind> {
+    prop_oneof![circle(), aabb(), polygon()]
+}
+
+fn position() -> impl Strategy<Value = Point<f32>> {
+    (-4f32..4., -4f32..4.).prop_map(|(x, y)| Point::new(x, y))
+}
+
+/// Offsets that keep most pairs of shapes overlapping.
+fn nearby() -> impl Strategy<Value = Vector<f32>> {
+    (-1f32..1., -1f32..1.).prop_map(|(x, y)| Vector::new(x, y))
+}
+
+/// Corners and radius of a shape, which is the polygon of the corners grown by the radius.
+fn rounded_polygon(shape: &ShapeKind, pos: Point<f32>) -> (Vec<Vector<f32>>, f32) {
+    match shape {
+        ShapeKind::Circle(circle) => (vec![pos.coords + circle.center.coords], circle.radius),
+        ShapeKind::Aabb(aabb) => (
+            aabb.corners()
+                .iter()
+                .map(|p| pos.coords + p.coords)
+                .collect(),
+            0.,
+        ),
+        ShapeKind::ConvexPolygon(polygon) => (
+            polygon
+                .points
+                .iter()
+                .map(|p| pos.coords + p.coords)
+                .collect(),
+            0.,
+        ),
+        ShapeKind::ConvexHull(hull) => (
+            hull.points()
+                .iter()
+                .map(|p| pos.coords + p.coords)
+                .collect(),
+            0.,
+        ),
+        ShapeKind::Point(point) => (vec![pos.coords + point.coords], 0.),
+    }
+}
+
+/// Signed distance from the origin to the boundary of the Minkowski difference `a - b` along `dir`.
+fn extent(a: &(Vec<Vector<f32>>, f32), b: &(Vec<Vector<f32>>, f32), dir: Vector<f32>) -> f32 {
+    let max_a = a.0.iter().map(|p| p.dot(&dir)).fold(f32::MIN, f32::max);
+    let min_b = b.0.iter().map(|p| p.dot(&dir)).fold(f32::MAX, f32::min);
+    max_a - min_b + a.1 + b.1
+}
+
+/// Penetration depth of the shapes, or their negated distance if they don't touch.
+///
+/// The closest point of the difference to the origin is either on an edge or on the rounding of a corner,
+/// so edge normals and the directions between corners are enough axes to test.
+fn oracle(a: (&ShapeKind, Point<f32>), b: (&ShapeKind, Point<f32>)) -> f32 {
+    let a = rounded_polygon(a.0, a.1);
+    let b = rounded_polygon(b.0, b.1);
+    let mut axes = Vec::new();
+    for points in &[&a.0, &b.0] {
+        for (i, &from) in points.iter().enumerate() {
+            let edge = points[(i + 1) % points.len()] - from;
+            if edge.norm() > 0. {
+                axes.push(Vector::new(edge.y, -edge.x).normalize());
+            }
+        }
+    }
+    for &p in &a.0 {
+        for &q in &b.0 {
+            if (p - q).norm() > 0. {
+                axes.push((p - q).normalize());
+            }
+        }
+    }
+    axes.iter()
+        .flat_map(|&axis| vec![axis, -axis])
+        .map(|axis| extent(&a, &b, axis))
+        .fold(f32::MAX, f32::min)
+}
+
+proptest! {
+    // Shrunk failures are saved next to this file and rerun first.
+    #![proptest_config(ProptestConfig {
+        failure_persistence: Some(Box::new(FileFailurePersistence::WithSource("regressions"))),
+        max_global_rejects: 100_000,
+        ..ProptestConfig::default()
+    })]
+
+    #[test]
+    fn gjk_agrees_with_oracle(a in shape(), b in shape(), a_pos in position(), b_pos in position()) {
+        let depth = oracle((&a, a_pos), (&b, b_pos));
+        prop_assume!(depth.abs() > MARGIN);
+        prop_assert_eq!(collides((&a, a_pos), (&b, b_pos)), depth > 0., "oracle depth {}", depth);
+    }
+
+    #[test]
+    fn epa_agrees_with_oracle(a in shape(), b in shape(), a_pos in position(), offset in nearby()) {
+        let b_pos = a_pos + offset;
+        let depth = oracle((&a, a_pos), (&b, b_pos));
+        prop_assume!(depth > MARGIN);
+        let (_, simplex) = collides_internal((&a, a_pos), (&b, b_pos));
+        let (normal, d) = solve((&a, a_pos), (&b, b_pos), simplex);
+        prop_assert!((d - depth).abs() < DEPTH_TOLERANCE, "depth {} but oracle found {}", d, depth);
+        // Several normals can reach the same depth, so check that this one does instead of which one it is.
+        let reached = extent(
+            &rounded_polygon(&a, a_pos),
+            &rounded_polygon(&b, b_pos),
+            normal,
+        );
+        prop_assert!(
+            (reached - depth).abs() < DEPTH_TOLERANCE,
+            "normal {} reaches {} but oracle found {}",
+            normal,
+            reached,
+            depth
+        );
+    }
+}
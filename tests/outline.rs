@@ -0,0 +1,73 @@
+use kolli_desu::outline::{minkowski_outline, outline};
+use kolli_desu::shapes::{Aabb, Circle, ConvexPolygon};
+use kolli_desu::{Point, Vector};
+
+#[test]
+fn circle_outline_is_within_tolerance() {
+    let circle = Circle::new(Point::new(0.5, 0.), 2.);
+    let pos = Point::new(1., 1.);
+    for &tolerance in &[0.1, 0.01, 0.001] {
+        let points = outline((&circle, pos), tolerance);
+        let center = pos + circle.center.coords;
+        for (a, b) in points
+            .iter()
+            .zip(points.iter().skip(1).chain(points.first()))
+        {
+            assert!(((a - center).norm() - circle.radius).abs() < 0.0001);
+            let middle = a + (b - a) / 2.;
+            assert!(circle.radius - (middle - center).norm() <= tolerance);
+            // Counterclockwise
+            let (a, b) = (a - center, b - center);
+            assert!(a.x * b.y - a.y * b.x > 0.);
+        }
+    }
+    assert!(outline((&circle, pos), 0.001).len() > outline((&circle, pos), 0.1).len());
+}
+
+#[test]
+fn polygon_outline_is_its_corners() {
+    let polygon = ConvexPolygon::new(vec![
+        Point::new(0., 0.),
+        Point::new(2., 0.),
+        Point::new(3., 1.),
+        Point::new(1., 2.),
+    ]);
+    let points = outline((&polygon, Point::new(0., 0.)), 0.001);
+    assert_eq!(points.len(), polygon.points.len());
+    for point in &polygon.points {
+        assert!(points.contains(point));
+    }
+}
+
+#[test]
+fn corner_off_the_middle_direction_is_found() {
+    // (9, 0.95) isn't the support point along (1, 1) but sticks out of the line from (10, 0) to (0, 1).
+    let polygon = ConvexPolygon::new(vec![
+        Point::new(10., 0.),
+        Point::new(9., 0.95),
+        Point::new(0., 1.),
+        Point::new(-1., 0.),
+        Point::new(0., -1.),
+    ]);
+    let points = outline((&polygon, Point::new(0., 0.)), 0.1);
+    assert!(points.contains(&Point::new(9., 0.95)));
+}
+
+#[test]
+fn minkowski_outline_of_boxes_is_a_box() {
+    let aabb = Aabb::new(Point::new(0., 0.), Point::new(1., 1.));
+    let points = minkowski_outline(
+        (&aabb, Point::new(0., 0.)),
+        (&aabb, Point::new(0.5, 0.)),
+        0.001,
+    );
+    assert_eq!(
+        points,
+        vec![
+            Vector::new(0.5, 1.),
+            Vector::new(-1.5, 1.),
+            Vector::new(-1.5, -1.),
+            Vector::new(0.5, -1.),
+        ]
+    );
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "rayon")]
+
+use std::sync::Arc;
+use std::thread;
+
+use kolli_desu::gjk::collides;
+use kolli_desu::parallel::{collides_pairs, colliding_pairs, penetrations};
+use kolli_desu::shapes::{Aabb, Circle, ConvexPolygon, SyncShape};
+use kolli_desu::Point;
+
+fn shapes() -> Vec<(Box<SyncShape>, Point<f32>)> {
+    let mut shapes: Vec<(Box<SyncShape>, Point<f32>)> = vec![];
+    for i in 0..60 {
+        let pos = Point::new((i % 10) as f32 * 0.7, (i / 10) as f32 * 0.6);
+        let shape: Box<SyncShape> = match i % 3 {
+            0 => Box::new(Circle::new(Point::new(0., 0.), 0.4)),
+            1 => Box::new(Aabb::new(Point::new(-0.3, -0.2), Point::new(0.3, 0.35))),
+            _ => Box::new(ConvexPolygon::new_rectangle(
+                Point::new(0., 0.),
+                Point::new(0.5, 0.3),
+                0.2,
+            )),
+        };
+        shapes.push((shape, pos));
+    }
+    shapes
+}
+
+#[test]
+fn parallel_pairs_agree_with_serial() {
+    let shapes = shapes();
+    let posed: Vec<_> = shapes.iter().map(|(s, p)| (&**s, *p)).collect();
+    let mut pairs = vec![];
+    for &a in &posed {
+        for &b in &posed {
+            pairs.push((a, b));
+        }
+    }
+    let serial: Vec<_> = pairs.iter().map(|&(a, b)| collides(a, b)).collect();
+    assert_eq!(collides_pairs(&pairs), serial);
+    let depths = penetrations(&pairs);
+    for (depth, collides) in depths.iter().zip(&serial) {
+        assert_eq!(depth.is_some(), *collides);
+    }
+
+    let mut expected = vec![];
+    for i in 0..posed.len() {
+        for j in i + 1..posed.len() {
+            if collides(posed[i], posed[j]) {
+                expected.push((i, j));
+            }
+        }
+    }
+    assert!(!expected.is_empty());
+    assert_eq!(colliding_pairs(&posed), expected);
+}
+
+#[test]
+fn shared_shapes_between_threads() {
+    let shape: Arc<SyncShape> = Arc::new(Circle::new(Point::new(0., 0.), 0.5));
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let shape = shape.clone();
+            thread::spawn(move || {
+                let other = Point::new(0., 0.);
+                collides((&shape, Point::new(i as f32 * 0.3, 0.)), (&other, other))
+            })
+        })
+        .collect();
+    let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(results, vec![true, true, false, false]);
+}
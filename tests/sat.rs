@@ -0,0 +1,139 @@
+use nalgebra::Isometry2;
+
+use kolli_desu::dispatch::penetration;
+use kolli_desu::epa::solve;
+use kolli_desu::gjk::collides_internal;
+use kolli_desu::sat::{polygon_circle, polygon_polygon};
+use kolli_desu::shapes::{Aabb, Circle, ConvexPolygon, Shape};
+use kolli_desu::{Point, Vector};
+
+const TAU: f32 = 2. * ::std::f32::consts::PI;
+
+fn assert_penetration(result: Option<(Vector<f32>, f32)>, normal: Vector<f32>, depth: f32) {
+    let (vector, d) = result.expect("Shapes should collide");
+    assert!(
+        (vector - normal).norm() < 0.0001,
+        "Normal {} wasn't close enough to {}",
+        vector,
+        normal
+    );
+    assert!(
+        (d - depth).abs() < 0.0001,
+        "Depth {} wasn't close enough to {}",
+        d,
+        depth
+    );
+}
+
+#[test]
+fn aabb_aabb_penetration() {
+    let a = Aabb::new(Point::new(0., 0.), Point::new(2., 2.)).to_polygon();
+    let b = Aabb::new(Point::new(1.5, 0.5), Point::new(3., 1.)).to_polygon();
+    assert_penetration(
+        polygon_polygon((&a, Point::new(0., 0.)), (&b, Point::new(0., 0.))),
+        Vector::new(1., 0.),
+        0.5,
+    );
+    assert_penetration(
+        polygon_polygon((&b, Point::new(0., 0.)), (&a, Point::new(0., 0.))),
+        Vector::new(-1., 0.),
+        0.5,
+    );
+    assert!(polygon_polygon((&a, Point::new(0., 0.)), (&b, Point::new(1., 0.))).is_none());
+}
+
+#[test]
+fn polygon_circle_penetration() {
+    let square = ConvexPolygon::new(vec![
+        Point::new(-1., -1.),
+        Point::new(1., -1.),
+        Point::new(1., 1.),
+        Point::new(-1., 1.),
+    ]);
+    let circle = Circle::new(Point::new(0., 0.), 0.5);
+    assert_penetration(
+        polygon_circle(
+            (&square, Point::new(0., 0.)),
+            (&circle, Point::new(0., 1.25)),
+        ),
+        Vector::new(0., 1.),
+        0.25,
+    );
+    let corner = Point::new(1.3, 1.3);
+    let depth = 0.5 - (0.3f32 * 0.3 * 2.).sqrt();
+    assert_penetration(
+        polygon_circle((&square, Point::new(0., 0.)), (&circle, corner)),
+        Vector::new(1., 1.).normalize(),
+        depth,
+    );
+    assert!(polygon_circle(
+        (&square, Point::new(0., 0.)),
+        (&circle, Point::new(1.4, 1.4))
+    )
+    .is_none());
+}
+
+#[test]
+fn sat_agrees_with_epa() {
+    let rectangle = ConvexPolygon::new_rectangle(Point::new(0., 0.), Point::new(1., 0.5), 0.5);
+    let aabb = Aabb::new(Point::new(-0.5, -0.5), Point::new(0.5, 0.5));
+    let circle = Circle::new(Point::new(0., 0.), 0.4);
+    let shapes: [&dyn Shape; 3] = [&rectangle, &aabb, &circle];
+    let steps = 36;
+    for &a in &shapes {
+        for &b in &shapes {
+            for n in 0..steps {
+                let pos = Isometry2::new(Vector::new(0., 0.), n as f32 * TAU / steps as f32)
+                    * Point::new(0.3, 0.);
+                let a = (a, Point::new(0., 0.));
+                let b = (b, pos);
+                let (collides, simplex) = collides_internal(a, b);
+                assert!(collides);
+                let (_, depth) = solve(a, b, simplex);
+                let (sat_normal, sat_depth) = penetration(a, b).unwrap();
+                assert!(
+                    (depth - sat_depth).abs() < 0.01,
+                    "Depth {} wasn't close enough to {}",
+                    sat_depth,
+                    depth
+                );
+                // Ties between axes can give different normals, but both have to separate the shapes.
+                let moved = (a.0, a.1 - sat_normal * (sat_depth + 0.001));
+                assert!(
+                    !collides_internal(moved, b).0,
+                    "Normal {} didn't separate the shapes",
+                    sat_normal
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn collinear_segments_separate_along_the_line() {
+    let a = ConvexPolygon::new(vec![Point::new(0., 0.), Point::new(1., 0.)]);
+    let b = ConvexPolygon::new(vec![Point::new(2., 0.), Point::new(3., 0.)]);
+    assert_eq!(
+        polygon_polygon((&a, Point::origin()), (&b, Point::origin())),
+        None
+    );
+    assert!(polygon_polygon((&a, Point::origin()), (&b, Point::new(-1.5, 0.))).is_some());
+}
+
+#[test]
+fn dispatch_falls_back_to_gjk() {
+    let circle = Circle::new(Point::new(0., 0.), 0.5);
+    let point = Point::new(0., 0.);
+    assert!(penetration((&circle, Point::new(0., 0.)), (&point, Point::new(0.2, 0.))).is_some());
+    assert!(penetration((&circle, Point::new(0., 0.)), (&point, Point::new(1., 0.))).is_none());
+}
+
+#[test]
+fn nan_circle_does_not_panic() {
+    let polygon = ConvexPolygon::new_rectangle(Point::new(-1., 0.), Point::new(1., 0.), 0.5);
+    let circle = Circle::new(Point::new(0., 0.), 1.);
+    polygon_circle(
+        (&polygon, Point::new(0., 0.)),
+        (&circle, Point::new(f32::NAN, 0.)),
+    );
+}
@@ -0,0 +1,58 @@
+use kolli_desu::sdf::{bake, signed_distance, Grid};
+use kolli_desu::shapes::{Aabb, Circle, ConvexPolygon, Shape};
+use kolli_desu::Point;
+
+fn assert_close(value: f32, correct: f32) {
+    assert!(
+        (value - correct).abs() < 0.0001,
+        "{} wasn't close enough to {}",
+        value,
+        correct
+    );
+}
+
+#[test]
+fn aabb_signed_distance() {
+    let aabb = Aabb::new(Point::new(0., 0.), Point::new(2., 1.));
+    assert_close(aabb.signed_distance(Point::new(3., 2.)), 2f32.sqrt());
+    assert_close(aabb.signed_distance(Point::new(1., 3.)), 2.);
+    assert_close(aabb.signed_distance(Point::new(1.8, 0.5)), -0.2);
+    assert_close(aabb.signed_distance(Point::new(2., 0.5)), 0.);
+}
+
+#[test]
+fn shapes_agree_on_signed_distance() {
+    let aabb = Aabb::new(Point::new(-1., -1.), Point::new(1., 1.));
+    let polygon = ConvexPolygon::new(vec![
+        Point::new(-1., -1.),
+        Point::new(1., -1.),
+        Point::new(1., 1.),
+        Point::new(-1., 1.),
+    ]);
+    for &point in &[
+        Point::new(0., 0.),
+        Point::new(0.5, -0.2),
+        Point::new(3., 0.),
+        Point::new(-2., 4.),
+    ] {
+        assert_close(aabb.signed_distance(point), polygon.signed_distance(point));
+    }
+}
+
+#[test]
+fn bake_union_of_shapes() {
+    let left = Circle::new(Point::new(0., 0.), 1.);
+    let right = Circle::new(Point::new(0., 0.), 0.5);
+    let shapes: &[(&dyn Shape, Point<f32>)] =
+        &[(&left, Point::new(-2., 0.)), (&right, Point::new(2., 0.))];
+    assert_close(signed_distance(shapes, Point::new(0., 0.)), 1.);
+
+    let grid = Grid::new(Point::new(-4., -1.), 1., 9, 3);
+    let sdf = bake(shapes, &grid);
+    assert_eq!(sdf.len(), 27);
+    let row = &sdf[9..18];
+    let correct = [1., 0., -1., 0., 1., 0.5, -0.5, 0.5, 1.5];
+    for (value, correct) in row.iter().zip(&correct) {
+        assert_close(*value, *correct);
+    }
+}
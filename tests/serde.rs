@@ -0,0 +1,107 @@
+#![cfg(feature = "serde")]
+
+use kolli_desu::shapes::{Aabb, Circle, ConvexHull, ConvexPolygon, FixedPolygon, Shape, ShapeKind};
+use kolli_desu::simplex::Simplex;
+use kolli_desu::{Point, Vector};
+
+#[test]
+fn shape_collection_round_trips() {
+    let shapes: Vec<Box<dyn Shape>> = vec![
+        Box::new(Circle::new(Point::new(1., 2.), 3.)),
+        Box::new(Aabb::new(Point::new(-1., -2.), Point::new(3., 4.))),
+        Box::new(ConvexPolygon::new(vec![
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(0., 1.),
+        ])),
+    ];
+    let json = serde_json::to_string(&shapes).unwrap();
+    let loaded: Vec<Box<dyn Shape>> = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.len(), shapes.len());
+    for (shape, loaded) in shapes.iter().zip(&loaded) {
+        for &dir in &[Vector::new(1., 0.), Vector::new(-1., 0.5)] {
+            assert_eq!(shape.farthest_in_dir(dir), loaded.farthest_in_dir(dir));
+        }
+    }
+    let circle = loaded[0].downcast_ref::<Circle>().unwrap();
+    assert_eq!(circle.radius, 3.);
+    assert!(json.contains(r#""type":"Circle""#));
+}
+
+struct Custom;
+
+impl Shape for Custom {
+    fn start(&self) -> Vector<f32> {
+        Vector::new(0., 0.)
+    }
+    fn farthest_in_dir(&self, _dir: Vector<f32>) -> Vector<f32> {
+        Vector::new(0., 0.)
+    }
+}
+
+#[test]
+fn custom_shapes_fail_to_serialize() {
+    let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Custom)];
+    assert!(serde_json::to_string(&shapes).is_err());
+}
+
+#[test]
+fn shape_kinds_round_trip() {
+    let shapes = vec![
+        ShapeKind::Point(Point::new(1., 2.)),
+        ShapeKind::Circle(Circle::new(Point::new(1., 2.), 3.)),
+    ];
+    let json = serde_json::to_string(&shapes).unwrap();
+    assert_eq!(
+        json,
+        r#"[{"type":"Point","point":[1.0,2.0]},{"type":"Circle","center":[1.0,2.0],"radius":3.0}]"#
+    );
+    let loaded: Vec<ShapeKind> = serde_json::from_str(&json).unwrap();
+    match &loaded[..] {
+        [ShapeKind::Point(point), ShapeKind::Circle(circle)] => {
+            assert_eq!(*point, Point::new(1., 2.));
+            assert_eq!(circle.radius, 3.);
+        }
+        _ => panic!("{:?}", loaded),
+    }
+}
+
+#[test]
+fn hulls_and_fixed_polygons_round_trip() {
+    let triangle = [Point::new(0., 0.), Point::new(2., 0.), Point::new(0., 1.)];
+    let shapes: Vec<Box<dyn Shape>> = vec![
+        Box::new(ConvexHull::new(triangle.to_vec())),
+        Box::new(ShapeKind::from(FixedPolygon::new(triangle))),
+    ];
+    let json = serde_json::to_string(&shapes).unwrap();
+    assert_eq!(
+        json,
+        r#"[{"type":"ConvexHull","points":[[0.0,0.0],[2.0,0.0],[0.0,1.0]]},{"type":"ConvexPolygon","points":[[0.0,0.0],[2.0,0.0],[0.0,1.0]]}]"#
+    );
+    let loaded: Vec<ShapeKind> = serde_json::from_str(&json).unwrap();
+    match &loaded[..] {
+        [ShapeKind::ConvexHull(hull), ShapeKind::ConvexPolygon(polygon)] => {
+            assert_eq!(hull.points(), &triangle[..]);
+            assert_eq!(polygon.points, triangle.to_vec());
+        }
+        _ => panic!("{:?}", loaded),
+    }
+    let loaded: Vec<Box<dyn Shape>> = serde_json::from_str(&json).unwrap();
+    for shape in &loaded {
+        assert_eq!(
+            shape.farthest_in_dir(Vector::new(1., 0.)),
+            Vector::new(2., 0.)
+        );
+    }
+}
+
+#[test]
+fn simplex_round_trips() {
+    let simplex = Simplex::Line(Vector::new(1., 2.), Vector::new(3., 4.));
+    let json = serde_json::to_string(&simplex).unwrap();
+    let loaded: Simplex = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        (&loaded).into_iter().collect::<Vec<_>>(),
+        (&simplex).into_iter().collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,399 @@
+use kolli_desu::shapes::{Aabb, Circle, ConvexHull, ConvexPolygon, Feature, FixedPolygon, Shape};
+use kolli_desu::{Point, Vector};
+
+use std::f32::consts::PI;
+
+fn assert_close(value: f32, correct: f32) {
+    assert!(
+        (value - correct).abs() < 0.0001,
+        "{} wasn't close enough to {}",
+        value,
+        correct
+    );
+}
+
+#[test]
+fn circle_mass_properties() {
+    let circle = Circle::new(Point::new(1., 2.), 2.);
+    assert_close(circle.area(), 4. * PI);
+    assert_eq!(circle.centroid(), Point::new(1., 2.));
+    assert_close(circle.inertia(0.5), 0.5 * 4. * PI * 4. / 2.);
+}
+
+#[test]
+fn aabb_mass_properties() {
+    let aabb = Aabb::new(Point::new(-1., 0.), Point::new(3., 2.));
+    assert_close(aabb.area(), 8.);
+    assert_eq!(aabb.centroid(), Point::new(1., 1.));
+    assert_close(aabb.inertia(2.), 2. * 8. * (16. + 4.) / 12.);
+}
+
+#[test]
+fn polygon_matches_aabb_in_either_winding() {
+    let aabb = Aabb::new(Point::new(-1., 0.), Point::new(3., 2.));
+    let mut points = vec![
+        Point::new(-1., 0.),
+        Point::new(3., 0.),
+        Point::new(3., 2.),
+        Point::new(-1., 2.),
+    ];
+    for _ in 0..2 {
+        let polygon = ConvexPolygon::new(points.clone());
+        assert_close(polygon.area(), aabb.area());
+        assert!((polygon.centroid() - aabb.centroid()).norm() < 0.0001);
+        assert_close(polygon.inertia(2.), aabb.inertia(2.));
+        points.reverse();
+    }
+}
+
+#[test]
+fn triangle_mass_properties() {
+    let triangle = ConvexPolygon::new(vec![
+        Point::new(0., 0.),
+        Point::new(3., 0.),
+        Point::new(0., 3.),
+    ]);
+    assert_close(triangle.area(), 4.5);
+    assert!((triangle.centroid() - Point::new(1., 1.)).norm() < 0.0001);
+    // Right triangle with legs of length a: m * a^2 / 9 around the centroid.
+    assert_close(triangle.inertia(1.), 4.5 * 9. / 9.);
+}
+
+#[test]
+fn line_segment_mass_properties() {
+    let line = ConvexPolygon::new_line_segment(Point::new(0., 0.), Point::new(2., 2.));
+    assert_close(line.area(), 0.);
+    assert_eq!(line.centroid(), Point::new(1., 1.));
+    assert_close(line.inertia(1.), 0.);
+}
+
+/// Only implements the support function, so queries go through the generic fallbacks.
+struct Generic<S>(S);
+
+impl<S: Shape> Shape for Generic<S> {
+    fn start(&self) -> Vector<f32> {
+        self.0.start()
+    }
+    fn farthest_in_dir(&self, dir: Vector<f32>) -> Vector<f32> {
+        self.0.farthest_in_dir(dir)
+    }
+}
+
+#[test]
+fn circle_point_projection() {
+    let circle = Circle::new(Point::new(1., 0.), 1.);
+    assert!(circle.contains_point(Point::new(1.5, 0.5)));
+    assert!(!circle.contains_point(Point::new(2.5, 0.)));
+
+    let outside = circle.project_point(Point::new(4., 0.));
+    assert_eq!(outside.point, Point::new(2., 0.));
+    assert_close(outside.distance, 2.);
+    assert!(!outside.inside);
+    assert_eq!(outside.feature, Feature::Boundary);
+
+    let inside = circle.project_point(Point::new(1., 0.5));
+    assert_eq!(inside.point, Point::new(1., 1.));
+    assert_close(inside.distance, -0.5);
+    assert!(inside.inside);
+}
+
+#[test]
+fn aabb_point_projection() {
+    let aabb = Aabb::new(Point::new(0., 0.), Point::new(2., 1.));
+    assert!(aabb.contains_point(Point::new(1., 1.)));
+    assert!(!aabb.contains_point(Point::new(1., 1.1)));
+
+    let corner = aabb.project_point(Point::new(3., 2.));
+    assert_eq!(corner.point, Point::new(2., 1.));
+    assert_eq!(corner.feature, Feature::Vertex(2));
+    assert_close(corner.distance, 2f32.sqrt());
+
+    let side = aabb.project_point(Point::new(-1., 0.5));
+    assert_eq!(side.point, Point::new(0., 0.5));
+    assert_eq!(side.feature, Feature::Edge(3));
+
+    let inside = aabb.project_point(Point::new(1.8, 0.5));
+    assert_eq!(inside.point, Point::new(2., 0.5));
+    assert_eq!(inside.feature, Feature::Edge(1));
+    assert_close(inside.distance, -0.2);
+}
+
+#[test]
+fn polygon_point_projection() {
+    let triangle = ConvexPolygon::new(vec![
+        Point::new(0., 0.),
+        Point::new(0., 3.),
+        Point::new(3., 0.),
+    ]);
+    assert!(triangle.contains_point(Point::new(1., 1.)));
+    assert!(triangle.contains_point(Point::new(0., 1.)));
+    assert!(!triangle.contains_point(Point::new(2., 2.)));
+
+    let vertex = triangle.project_point(Point::new(-1., -1.));
+    assert_eq!(vertex.point, Point::new(0., 0.));
+    assert_eq!(vertex.feature, Feature::Vertex(0));
+
+    let edge = triangle.project_point(Point::new(2., 2.));
+    assert!((edge.point - Point::new(1.5, 1.5)).norm() < 0.0001);
+    assert_eq!(edge.feature, Feature::Edge(1));
+    assert_close(edge.distance, 0.5f32.sqrt());
+
+    let inside = triangle.project_point(Point::new(0.5, 1.));
+    assert_eq!(inside.point, Point::new(0., 1.));
+    assert_eq!(inside.feature, Feature::Edge(0));
+    assert_close(inside.distance, -0.5);
+}
+
+#[test]
+fn generic_point_projection_matches_analytic() {
+    let shapes: Vec<Box<dyn Shape>> = vec![
+        Box::new(Circle::new(Point::new(1., 0.), 1.)),
+        Box::new(Aabb::new(Point::new(0., 0.), Point::new(2., 1.))),
+        Box::new(ConvexPolygon::new_rectangle(
+            Point::new(0., 0.),
+            Point::new(1., 1.),
+            1.,
+        )),
+    ];
+    let points = [
+        Point::new(0.9, 0.4),
+        Point::new(3., 2.),
+        Point::new(-1., 0.5),
+        Point::new(0.5, -2.),
+    ];
+    for shape in shapes {
+        let generic = Generic(shape);
+        for &point in &points {
+            let analytic = generic.0.project_point(point);
+            let fallback = generic.project_point(point);
+            assert_eq!(analytic.inside, fallback.inside, "{}", point);
+            assert_eq!(fallback.feature, Feature::Unknown);
+            assert!(
+                (analytic.point - fallback.point).norm() < 0.01,
+                "{} != {}",
+                analytic.point,
+                fallback.point
+            );
+            assert!((analytic.distance - fallback.distance).abs() < 0.01);
+        }
+    }
+}
+
+fn regular_polygon(count: usize, radius: f32) -> ConvexPolygon {
+    ConvexPolygon::new(
+        (0..count)
+            .map(|i| {
+                let angle = i as f32 * 2. * PI / count as f32;
+                Point::new(angle.cos(), angle.sin()) * radius
+            })
+            .collect(),
+    )
+}
+
+#[test]
+fn hinted_support_matches_plain_support() {
+    let polygon = regular_polygon(256, 3.);
+    let mut hint = 0;
+    let steps = 1000;
+    for n in 0..steps {
+        let angle = n as f32 * 2. * PI / steps as f32;
+        let dir = Vector::new(angle.cos(), angle.sin());
+        hint = polygon.farthest_index_from(dir, hint);
+        let correct = polygon.farthest_in_dir(dir);
+        assert!(
+            (polygon.points[hint].coords.dot(&dir) - correct.dot(&dir)).abs() < 0.0001,
+            "{} != {}",
+            polygon.points[hint],
+            correct
+        );
+    }
+}
+
+#[test]
+fn hull_support_with_collinear_points() {
+    let hull = ConvexHull::new(vec![
+        Point::new(1., 0.),
+        Point::new(2., 0.),
+        Point::new(2., 2.),
+        Point::new(0., 2.),
+        Point::new(0., 0.),
+    ]);
+    assert_eq!(
+        hull.farthest_in_dir(Vector::new(-1., -0.1)),
+        Vector::new(0., 0.)
+    );
+
+    // Rectangle with a point in the middle of every side, starting from each point in either winding.
+    let mut points = vec![
+        Point::new(0., 0.),
+        Point::new(1., 0.),
+        Point::new(2., 0.),
+        Point::new(2., 1.),
+        Point::new(2., 2.),
+        Point::new(1., 2.),
+        Point::new(0., 2.),
+        Point::new(0., 1.),
+    ];
+    for _ in 0..2 {
+        for _ in 0..points.len() {
+            points.rotate_left(1);
+            let hull = ConvexHull::new(points.clone());
+            let steps = 3600;
+            for n in 0..steps {
+                let angle = n as f32 * 2. * PI / steps as f32;
+                let dir = Vector::new(angle.cos(), angle.sin());
+                let max = points
+                    .iter()
+                    .map(|p| p.coords.dot(&dir))
+                    .fold(f32::MIN, f32::max);
+                let found = hull.farthest_in_dir(dir);
+                assert!(
+                    (found.dot(&dir) - max).abs() < 0.0001,
+                    "{} isn't the farthest point of {:?} in direction {}",
+                    found,
+                    points,
+                    dir
+                );
+            }
+        }
+        points.reverse();
+    }
+}
+
+#[test]
+fn hull_support_matches_polygon_support() {
+    for &count in &[2, 3, 4, 17, 256] {
+        let polygon = regular_polygon(count, 2.);
+        let mut reversed = polygon.clone();
+        reversed.points.reverse();
+        for hull in [
+            ConvexHull::from(polygon.clone()),
+            ConvexHull::from(reversed),
+        ] {
+            let steps = 720;
+            for n in 0..steps {
+                let angle = n as f32 * 2. * PI / steps as f32 + 0.001;
+                let dir = Vector::new(angle.cos(), angle.sin());
+                let correct = polygon.farthest_in_dir(dir);
+                let found = hull.farthest_in_dir(dir);
+                assert!(
+                    (found.dot(&dir) - correct.dot(&dir)).abs() < 0.0001,
+                    "{} != {} with {} points in direction {}",
+                    found,
+                    correct,
+                    count,
+                    dir
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn fixed_polygon_matches_polygon() {
+    let polygon = regular_polygon(6, 2.);
+    let mut points = [Point::origin(); 6];
+    points.copy_from_slice(&polygon.points);
+    let fixed = FixedPolygon::new(points);
+    assert_close(fixed.area(), polygon.area());
+    assert_close(fixed.inertia(2.), polygon.inertia(2.));
+    for n in 0..16 {
+        let angle = n as f32 * 2. * PI / 16.;
+        let dir = Vector::new(angle.cos(), angle.sin());
+        assert_eq!(fixed.farthest_in_dir(dir), polygon.farthest_in_dir(dir));
+        let point = Point::from(dir * 3.);
+        let projection = fixed.project_point(point);
+        assert_eq!(projection.point, polygon.project_point(point).point);
+        assert!(!projection.inside);
+    }
+}
+
+#[test]
+fn support_sees_past_duplicate_points() {
+    let polygon = ConvexPolygon::new(vec![
+        Point::new(1., 0.),
+        Point::new(1., 0.),
+        Point::new(0., 1.),
+        Point::new(-1., 0.),
+        Point::new(0., -1.),
+    ]);
+    assert_eq!(
+        polygon.farthest_in_dir(Vector::new(1., 2.)),
+        Vector::new(0., 1.)
+    );
+    assert_eq!(
+        polygon.farthest_in_dir(Vector::new(1., -2.)),
+        Vector::new(0., -1.)
+    );
+}
+
+#[test]
+fn support_climbs_past_plateaus() {
+    // Collinear points along every side and duplicates on the corners.
+    let polygon = ConvexPolygon::new(vec![
+        Point::new(-1., -1.),
+        Point::new(0., -1.),
+        Point::new(1., -1.),
+        Point::new(1., -1.),
+        Point::new(1., 0.),
+        Point::new(1., 1.),
+        Point::new(0., 1.),
+        Point::new(-1., 1.),
+        Point::new(-1., 1.),
+        Point::new(-1., 0.),
+    ]);
+    let dirs = [
+        Vector::new(1., 0.),
+        Vector::new(0., 1.),
+        Vector::new(-1., 0.),
+        Vector::new(0., -1.),
+        Vector::new(1., 1.),
+        Vector::new(-1., 1.),
+        Vector::new(1., -0.001),
+    ];
+    for &dir in &dirs {
+        let farthest = polygon
+            .points
+            .iter()
+            .map(|p| p.coords.dot(&dir))
+            .fold(f32::MIN, f32::max);
+        assert_eq!(polygon.farthest_in_dir(dir).dot(&dir), farthest, "{}", dir);
+        for hint in 0..polygon.points.len() {
+            let index = polygon.farthest_index_from(dir, hint);
+            assert_eq!(
+                polygon.points[index].coords.dot(&dir),
+                farthest,
+                "{} from {}",
+                dir,
+                hint
+            );
+        }
+    }
+}
+
+#[test]
+fn nan_support_terminates() {
+    let polygon = ConvexPolygon::new(vec![
+        Point::new(0., 0.),
+        Point::new(f32::NAN, 1.),
+        Point::new(1., 1.),
+    ]);
+    polygon.farthest_in_dir(Vector::new(0., 1.));
+    polygon.farthest_in_dir(Vector::new(f32::NAN, 1.));
+}
+
+#[test]
+fn nan_projection_does_not_panic() {
+    let everything = Aabb::new(
+        Point::new(f32::NEG_INFINITY, f32::NEG_INFINITY),
+        Point::new(f32::INFINITY, f32::INFINITY),
+    );
+    everything.project_point(Point::new(f32::INFINITY, 0.));
+    let polygon = Generic(ConvexPolygon::new_rectangle(
+        Point::new(-1., 0.),
+        Point::new(1., 0.),
+        0.5,
+    ));
+    polygon.project_point(Point::new(f32::NAN, 0.));
+    polygon.project_point(Point::new(f32::INFINITY, 1.));
+}
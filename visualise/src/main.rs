@@ -1,249 +1,528 @@
-use std::collections::VecDeque;
-use std::path::Path;
+mod scene;
+mod svg;
 
+use std::collections::{HashMap, VecDeque};
+use std::env;
+use std::ffi::OsStr;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use image::{gif, ImageBuffer, Rgb, RgbImage};
 use kolli_desu::{
-    shapes::{Aabb, Circle, ConvexPolygon, Shape},
+    debug_draw::{draw_contact, draw_shape, Color, DebugRenderer},
+    dispatch::Contact,
+    epa, gjk,
+    outline::{minkowski_outline, outline, outline_with},
+    sdf::{bake, Grid},
+    shapes::{support, Shape, ShapeKind},
+    trace::{Event, Stats},
     Point, Vector,
 };
-use nalgebra::Isometry2;
+
+use crate::scene::Scene;
+use crate::svg::Svg;
 
 const TAU: f32 = 2. * ::std::f32::consts::PI;
 
-fn main() {
-    let imgx = 5000; //1920;
-    let imgy = 5000; //1080;
+const USAGE: &str =
+    "Usage: visualise SCENE [--output PATH] [--size PIXELS] [--scale PIXELS_PER_UNIT] [--trace]
+
+Renders the Minkowski difference of the two shapes in the SCENE json file with the GJK simplex and
+the EPA polytope to PATH, the shapes and the penetration vector to PATH_orig and their signed
+distance field to PATH_sdf.
+
+If PATH ends in .svg, everything but the distance field is drawn into that one file instead.
+
+With --trace, every step of GJK and EPA is also animated into PATH_trace.gif.";
+
+/// Colors of the EPA polytope edges, picked to be easy to tell apart.
+const PALETTE: &[[u8; 3]] = &[
+    [185, 255, 195],
+    [228, 52, 233],
+    [70, 242, 69],
+    [254, 0, 198],
+    [57, 203, 0],
+    [190, 56, 210],
+    [164, 248, 20],
+    [92, 111, 255],
+    [239, 255, 54],
+    [32, 143, 255],
+    [162, 209, 0],
+    [248, 137, 255],
+    [0, 182, 52],
+    [242, 0, 135],
+    [1, 240, 141],
+    [221, 53, 78],
+    [0, 246, 197],
+    [255, 124, 199],
+    [164, 255, 131],
+    [115, 110, 188],
+    [255, 218, 82],
+    [21, 126, 188],
+    [255, 156, 54],
+    [0, 149, 192],
+    [190, 91, 17],
+    [100, 255, 254],
+    [255, 107, 96],
+    [2, 194, 141],
+    [223, 168, 255],
+    [51, 136, 34],
+    [240, 209, 255],
+    [132, 131, 0],
+    [106, 213, 255],
+    [185, 137, 0],
+    [0, 183, 216],
+    [255, 212, 110],
+    [1, 178, 188],
+    [255, 170, 125],
+    [1, 152, 123],
+    [170, 97, 128],
+    [236, 255, 163],
+    [51, 131, 126],
+    [255, 205, 158],
+    [78, 129, 114],
+    [255, 226, 233],
+    [80, 131, 77],
+    [173, 249, 255],
+    [120, 123, 56],
+    [249, 255, 210],
+    [146, 112, 98],
+];
+
+struct Options {
+    scene: PathBuf,
+    output: PathBuf,
+    size: u32,
+    scale: f32,
+    trace: bool,
+}
 
-    // let shape1 = ConvexPolygon::new_line_segment(Point::new(-1., -1.), Point::new(1., 1.));
-    // let shape2 = ConvexPolygon::new_line_segment(Point::new(1., -1.), Point::new(-1., 1.));
+impl Options {
+    /// Parses the command line, or returns `None` if only the usage was asked for.
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut scene = None;
+        let mut output = PathBuf::from("visualise.png");
+        let mut size = 1000;
+        let mut scale = 200.;
+        let mut trace = false;
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("Missing value for {}", arg));
+            match arg.as_str() {
+                "--output" => output = value()?.into(),
+                "--size" => size = value()?.parse().map_err(|e| format!("--size: {}", e))?,
+                "--scale" => scale = value()?.parse().map_err(|e| format!("--scale: {}", e))?,
+                "--trace" => trace = true,
+                "--help" | "-h" => return Ok(None),
+                _ if scene.is_none() && !arg.starts_with('-') => scene = Some(arg.into()),
+                _ => return Err(format!("Unexpected argument {}\n\n{}", arg, USAGE)),
+            }
+        }
+        let scene = scene.ok_or_else(|| USAGE.to_owned())?;
+        Ok(Some(Options {
+            scene,
+            output,
+            size,
+            scale,
+            trace,
+        }))
+    }
 
-    // let shape1 = Aabb::new(Point::new(-0.1, 0.), Point::new(0.5, 0.5));
-    // let shape2 = Aabb::new(Point::new(-0.5, 0.), Point::new(0., 0.5));
+    /// Output path with `suffix` added to the file name.
+    fn output_with(&self, suffix: &str) -> PathBuf {
+        let stem = self
+            .output
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy();
+        let extension = self
+            .output
+            .extension()
+            .unwrap_or_default()
+            .to_string_lossy();
+        self.output
+            .with_file_name(format!("{}{}.{}", stem, suffix, extension))
+    }
+}
 
-    // let shape1 = Aabb::new(Point::new(-0.1, 0.1), Point::new(0.5, 0.5));
-    // let shape2 = Aabb::new(Point::new(-0.5, 0.), Point::new(0., 0.5));
+/// Maps shape space to pixels, with the origin in the middle of the image and y pointing up.
+#[derive(Clone)]
+struct Canvas {
+    image: RgbImage,
+    scale: f32,
+}
 
-    // let shape1 = Aabb::new(Point::new(-0.5, -0.5), Point::new(0.5, 0.5));
-    // let shape2 = Circle::new(Point::new(0.1, 0.1), 1.);
+impl Canvas {
+    fn new(size: u32, scale: f32) -> Self {
+        Canvas {
+            image: ImageBuffer::from_pixel(size, size, Rgb([25, 25, 25])),
+            scale,
+        }
+    }
 
-    // let shape1 = Aabb::new(Point::new(-0.5, -0.5), Point::new(0., 0.));
-    // let shape2 = Circle::new(Point::new(1., 1.), 0.5);
+    fn transform(&self, v: Vector<f32>) -> Vector<f32> {
+        Vector::new(
+            self.image.width() as f32 / 2. + v.x * self.scale,
+            self.image.height() as f32 / 2. - v.y * self.scale,
+        )
+    }
 
-    // let shape1 = Aabb::new(Point::new(-0.5, -0.5), Point::new(0.0, 0.5));
-    // let shape2 = Circle::new(Point::new(0.5, 0.), 0.4);
+    /// Sets the pixel at already transformed `p`, skipping ones outside of the image.
+    fn plot(&mut self, p: Vector<f32>, color: Rgb<u8>) {
+        if p.x >= 0. && p.y >= 0. {
+            let (x, y) = (p.x as u32, p.y as u32);
+            if x < self.image.width() && y < self.image.height() {
+                self.image[(x, y)] = color;
+            }
+        }
+    }
 
-    // let shape1 = Aabb::new(Point::new(-0.5, -0.5), Point::new(0.5, 0.5));
-    // let shape2 = ConvexPolygon::new_rectangle(Point::new(-1., -1.), Point::new(-0.4, 0.), 1.);
+    /// Draws a line between shape space points, colored by how far from `to` towards `from` each pixel is.
+    ///
+    /// Pixels whose color is `None` are skipped.
+    fn line(&mut self, from: Vector<f32>, to: Vector<f32>, color: impl Fn(f32) -> Option<Rgb<u8>>) {
+        let (from, to) = (self.transform(from), self.transform(to));
+        let steps = ((to - from).norm() as usize * 2).max(1);
+        for n in 0..=steps {
+            let f = n as f32 / steps as f32;
+            if let Some(color) = color(f) {
+                self.plot(from * f + to * (1. - f), color);
+            }
+        }
+    }
 
-    // let shape1 = ConvexPolygon::new_rectangle(Point::new(0., 0.), Point::new(1., 1.), 2f32.sqrt());
-    // let shape2 = Circle::new(Point::new(2., 1.), 1.1);
+    /// Draws a closed loop through shape space points, coloring each edge with the next color.
+    fn polygon(&mut self, points: &[Vector<f32>], colors: &mut VecDeque<Rgb<u8>>, dashed: bool) {
+        for (&from, &to) in points
+            .iter()
+            .zip(points.iter().skip(1).chain(points.first()))
+        {
+            let color = colors.pop_back().unwrap();
+            colors.push_front(color);
+            let length = (self.transform(to) - self.transform(from)).norm();
+            self.line(from, to, |f| {
+                if dashed && ((f * length / 20.) as u32).is_multiple_of(2) {
+                    None
+                } else {
+                    Some(color)
+                }
+            });
+        }
+    }
 
-    // let shape1 = Point::new(1., -1.);
-    // let shape2 = ConvexPolygon::new_rectangle(Point::new(-1., -1.), Point::new(1., -1.), 2.);
+    /// Marks a shape space point with a small cross.
+    fn cross(&mut self, p: Vector<f32>, color: Rgb<u8>) {
+        let size = 4. / self.scale;
+        for &offset in &[Vector::new(size, size), Vector::new(size, -size)] {
+            self.line(p - offset, p + offset, |_| Some(color));
+        }
+    }
 
-    // let shape1 = Circle::new(Point::new(-0.75, 0.), 1.);
-    // let shape2 = Circle::new(Point::new(0.75, 0.), 1.);
+    /// Traces the outline of a shape given by its support function, to within half a pixel.
+    fn outline(&mut self, support: impl Fn(Vector<f32>) -> Vector<f32>, color: Rgb<u8>) {
+        let points = outline_with(support, 0.5 / self.scale);
+        if let [point] = points[..] {
+            let p = self.transform(point);
+            self.plot(p, color);
+        }
+        for (i, &from) in points.iter().enumerate() {
+            let to = points[(i + 1) % points.len()];
+            self.line(from, to, |_| Some(color));
+        }
+    }
 
-    // let shape1 = Circle::new(Point::new(0., 0.), 0.5);
-    // let shape2 = Circle::new(Point::new(0., 0.), 0.5);
+    fn save(self, path: &Path) -> Result<(), String> {
+        image::ImageRgb8(self.image)
+            .save(path)
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
 
-    // let shape1 = Circle::new(Point::new(0., 1.5), 0.5);
-    // let shape2 = Circle::new(Point::new(0., 0.), 0.5);
+/// PNGs have no font to draw with, so text is left to the SVG output.
+impl DebugRenderer for Canvas {
+    fn line(&mut self, from: Point<f32>, to: Point<f32>, color: Color) {
+        Canvas::line(self, from.coords, to.coords, |_| Some(rgb(color)));
+    }
 
-    // let shape1 = Circle::new(Point::new(0.1, 0.1), 0.5);
-    // let shape2 = Circle::new(Point::new(0., 0.), 0.5);
+    fn circle(&mut self, center: Point<f32>, radius: f32, color: Color) {
+        let steps = ((TAU * radius * self.scale) as usize * 2).max(16);
+        for i in 0..steps {
+            let angle = i as f32 / steps as f32 * TAU;
+            let p = center.coords + Vector::new(angle.cos(), angle.sin()) * radius;
+            let p = self.transform(p);
+            self.plot(p, rgb(color));
+        }
+    }
 
-    // let shape1 = Circle::new(Point::new(0., 0.), 0.5);
-    // let mut shape2 = Circle::new(Point::new(1., 0.), 0.5);
-    // shape2.center = Isometry2::new(Vector::new(0., 0.), 45. * TAU / 360.) * shape2.center;
+    fn polygon(&mut self, points: &[Point<f32>], color: Color) {
+        for (from, to) in points
+            .iter()
+            .zip(points.iter().skip(1).chain(points.first()))
+        {
+            DebugRenderer::line(self, *from, *to, color);
+        }
+    }
 
-    let shape1 =
-        ConvexPolygon::new_rectangle(Point::new(0., 0.), Point::new(0.5, 0.5), 1f32.sqrt());
-    let shape2 = Circle::new(Point::new(1., 0.5), 0.55);
+    fn point(&mut self, point: Point<f32>, color: Color) {
+        self.cross(point.coords, rgb(color));
+    }
 
-    let mut imgbuf = image::ImageBuffer::from_pixel(imgx, imgy, image::Rgb([25, 25, 25]));
+    fn text(&mut self, _at: Point<f32>, _text: &str, _color: Color) {}
+}
 
-    let scale = 1000.;
-    let transform = |v: Vector<_>| {
-        Vector::new(
-            imgx as f32 / 2. + v.x * scale,
-            imgy as f32 / 2. - v.y * scale,
-        )
+fn rgb(color: Color) -> Rgb<u8> {
+    Rgb([color[0], color[1], color[2]])
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), String> {
+    let options = match Options::parse(env::args().skip(1))? {
+        Some(options) => options,
+        None => {
+            println!("{}", USAGE);
+            return Ok(());
+        }
     };
+    let scene = Scene::load(&options.scene)?;
+    let (a, b) = (scene.a.pose(), scene.b.pose());
 
-    let (collides, simplex1) = ::kolli_desu::gjk::collides_internal(
-        (&shape1, Point::new(0., 0.)),
-        (&shape2, Point::new(0., 0.)),
-    );
+    let mut stats = Stats::default();
+    let (collides, simplex) = gjk::collides_traced(a, b, &mut stats);
     println!("collides: {}", collides);
+    println!("gjk: {:?}", stats);
     println!(
         "{:?}",
-        (&simplex1)
+        (&simplex)
             .into_iter()
             .map(|v| (v.x, v.y))
             .collect::<Vec<_>>()
     );
-    let (penetration, depth, simplex) = ::kolli_desu::epa::solve_internal(
-        (&shape1, Point::new(0., 0.)),
-        (&shape2, Point::new(0., 0.)),
-        simplex1.clone(),
-    );
-    println!(
-        "{}: {:?}",
-        simplex.len(),
-        simplex.iter().map(|v| (v.x, v.y)).collect::<Vec<_>>()
-    );
-    println!("penetration: {}, depth: {}", penetration, depth);
+    let penetration = if collides {
+        let mut stats = Stats::default();
+        let (normal, depth, polytope) = epa::solve_traced(a, b, simplex.clone(), &mut stats);
+        println!("epa: {:?}", stats);
+        println!(
+            "{}: {:?}",
+            polytope.len(),
+            polytope.iter().map(|v| (v.x, v.y)).collect::<Vec<_>>()
+        );
+        println!("penetration: {}, depth: {}", normal, depth);
+        Some((normal, depth, polytope))
+    } else {
+        None
+    };
+    let simplex: Vec<_> = simplex.into_iter().collect();
 
-    let mut colors = VecDeque::new();
-    for c in &[[255, 255, 0], [0, 255, 255], [255, 0, 255]][..] {
-        colors.push_back(image::Rgb(*c));
+    if options.trace {
+        render_trace(&options, a, b)?;
     }
-    let points1 = simplex1.into_iter().map(transform).collect::<Vec<_>>();
-    let steps = 10000;
-    for (from, to) in points1
-        .iter()
-        .zip(points1.iter().skip(1).chain(points1.first()))
-    {
-        let color = colors.pop_back().unwrap();
-        colors.push_front(color);
-        for n in 0..steps {
-            if n % 400 < 200 {
-                continue;
-            }
-            let f = n as f32 / steps as f32;
-            let p = from * f + to * (1. - f);
-            imgbuf[(p.x as u32, p.y as u32)] = color;
-        }
-    }
-
-    let points = simplex.into_iter().map(transform).collect::<Vec<_>>();
-    let mut colors = VecDeque::new();
-    for c in &[
-        [185, 255, 195],
-        [228, 52, 233],
-        [70, 242, 69],
-        [254, 0, 198],
-        [57, 203, 0],
-        [190, 56, 210],
-        [164, 248, 20],
-        [92, 111, 255],
-        [239, 255, 54],
-        [32, 143, 255],
-        [162, 209, 0],
-        [248, 137, 255],
-        [0, 182, 52],
-        [242, 0, 135],
-        [1, 240, 141],
-        [221, 53, 78],
-        [0, 246, 197],
-        [255, 124, 199],
-        [164, 255, 131],
-        [115, 110, 188],
-        [255, 218, 82],
-        [21, 126, 188],
-        [255, 156, 54],
-        [0, 149, 192],
-        [190, 91, 17],
-        [100, 255, 254],
-        [255, 107, 96],
-        [2, 194, 141],
-        [223, 168, 255],
-        [51, 136, 34],
-        [240, 209, 255],
-        [132, 131, 0],
-        [106, 213, 255],
-        [185, 137, 0],
-        [0, 183, 216],
-        [255, 212, 110],
-        [1, 178, 188],
-        [255, 170, 125],
-        [1, 152, 123],
-        [170, 97, 128],
-        [236, 255, 163],
-        [51, 131, 126],
-        [255, 205, 158],
-        [78, 129, 114],
-        [255, 226, 233],
-        [80, 131, 77],
-        [173, 249, 255],
-        [120, 123, 56],
-        [249, 255, 210],
-        [146, 112, 98],
-    ][..]
-    {
-        colors.push_back(image::Rgb(*c));
-    }
-    let steps = 10000;
-    for (from, to) in points
+    if options.output.extension() == Some(OsStr::new("svg")) {
+        return render_svg(&options, a, b, &simplex, penetration.as_ref());
+    }
+
+    let mut canvas = Canvas::new(options.size, options.scale);
+    let mut colors: VecDeque<_> = [[255, 255, 0], [0, 255, 255], [255, 0, 255]]
         .iter()
-        .zip(points.iter().skip(1).chain(points.first()))
-    {
-        let color = colors.pop_back().unwrap();
-        colors.push_front(color);
-        for n in 0..steps {
-            let f = n as f32 / steps as f32;
-            let p = from * f + to * (1. - f);
-            imgbuf[(p.x as u32, p.y as u32)] = color;
-        }
+        .map(|&c| Rgb(c))
+        .collect();
+    canvas.polygon(&simplex, &mut colors, true);
+    if let Some((_, _, polytope)) = &penetration {
+        let mut colors = PALETTE.iter().map(|&c| Rgb(c)).collect();
+        canvas.polygon(polytope, &mut colors, false);
     }
-    let steps = 36000;
-    for i in 0..steps {
-        let i = (i as f32 / steps as f32) * TAU;
-        let dir = Vector::new(i.sin(), i.cos());
+    canvas.outline(|dir| support(a, b, dir), Rgb([255, 0, 0]));
+    canvas.cross(Vector::new(0., 0.), Rgb([0, 255, 0]));
+    canvas.save(&options.output)?;
+
+    let mut canvas = Canvas::new(options.size, options.scale);
+    draw_shape(&mut canvas, (a.0, a.1), [255, 0, 0, 255]);
+    draw_shape(&mut canvas, (b.0, b.1), [0, 0, 255, 255]);
+    if let Some((normal, depth, _)) = penetration {
+        let from = posed_support(a, normal);
+        let contact = Contact {
+            normal,
+            depth,
+            point: Point::from(from - normal * (depth / 2.)),
+        };
+        draw_contact(&mut canvas, &contact, [0, 255, 128, 255]);
+    }
+    canvas.save(&options.output_with("_orig"))?;
+
+    let sdf_size = 500;
+    let extent = options.size as f32 / options.scale;
+    let grid = Grid::new(
+        Point::new(-extent / 2., -extent / 2.),
+        extent / sdf_size as f32,
+        sdf_size,
+        sdf_size,
+    );
+    let sdf = bake(&[a, b], &grid);
+    let image = ImageBuffer::from_fn(sdf_size as u32, sdf_size as u32, |x, y| {
+        // Flip y so that the image matches the other outputs.
+        let distance = sdf[(sdf_size - 1 - y as usize) * sdf_size + x as usize];
+        let shade = (255. * (1. - (distance.abs() * 2.).min(1.))) as u8;
+        let band = if (distance * 10.).fract().abs() < 0.05 {
+            60
+        } else {
+            0
+        };
+        if distance < 0. {
+            Rgb([shade.max(80), band, 0])
+        } else {
+            Rgb([band, band, shade])
+        }
+    });
+    let path = options.output_with("_sdf");
+    image::ImageRgb8(image)
+        .save(&path)
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
 
-        // let val = shape1.farthest_in_dir(dir);
-        let val = kolli_desu::shapes::support(
-            (&shape1, Point::new(0., 0.)),
-            (&shape2, Point::new(0., 0.)),
-            dir,
-        );
-        let p = transform(val);
-        imgbuf[(p.x as u32, p.y as u32)] = image::Rgb([255, 0, 0]);
+/// Draws one frame for every step of GJK and EPA on top of the Minkowski difference.
+fn render_trace(
+    options: &Options,
+    a: (&ShapeKind, Point<f32>),
+    b: (&ShapeKind, Point<f32>),
+) -> Result<(), String> {
+    let mut events = vec![];
+    let (collides, simplex) = gjk::collides_traced(a, b, &mut events);
+    if collides {
+        epa::solve_traced(a, b, simplex, &mut events);
     }
 
-    let zero = transform(Vector::new(0., 0.));
-    for (x, y) in &[(0., 1.), (-1., 0.), (0., 0.), (1., 0.), (0., -1.)] {
-        imgbuf[((zero.x + x) as u32, (zero.y + y) as u32)] = image::Rgb([0, 255, 0]);
+    let mut base = Canvas::new(options.size, options.scale);
+    base.outline(|dir| support(a, b, dir), Rgb([255, 0, 0]));
+    let zero = Vector::new(0., 0.);
+    base.cross(zero, Rgb([0, 255, 0]));
+
+    let path = options.output_with("_trace").with_extension("gif");
+    let file = File::create(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let mut encoder = gif::Encoder::new(file);
+    let (mut simplex, mut polytope) = (vec![], vec![]);
+    let (mut direction, mut point, mut edge) = (None, None, None);
+    for event in events {
+        match event {
+            Event::Direction(dir) => direction = Some(dir),
+            Event::Support(support) => point = Some(support),
+            Event::Simplex(s) => simplex = s.into_iter().collect(),
+            Event::Polytope(p) => polytope = p,
+            Event::ClosestEdge { from, to, .. } => edge = Some((from, to)),
+            Event::PolytopeSize(_) => continue,
+        }
+        let mut frame = base.clone();
+        let mut colors = [[255, 255, 0], [0, 255, 255], [255, 0, 255]]
+            .iter()
+            .map(|&c| Rgb(c))
+            .collect();
+        frame.polygon(&simplex, &mut colors, true);
+        let mut colors = PALETTE.iter().map(|&c| Rgb(c)).collect();
+        frame.polygon(&polytope, &mut colors, false);
+        if let Some((from, to)) = edge {
+            frame.line(from, to, |_| Some(Rgb([255, 255, 255])));
+        }
+        if let Some(dir) = direction {
+            let to = dir.normalize() * (50. / options.scale);
+            frame.line(zero, to, |_| Some(Rgb([0, 255, 0])));
+        }
+        if let Some(point) = point {
+            frame.cross(point, Rgb([255, 0, 255]));
+        }
+        let mut gif_frame = gif_frame(&frame.image);
+        gif_frame.delay = 50;
+        encoder
+            .encode(&gif_frame)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
     }
+    Ok(())
+}
 
-    let p = "visualise.png";
-    let fout = Path::new(&p);
+/// Indexes the colors of the image directly, which is much faster than quantizing them when there are few.
+fn gif_frame(image: &RgbImage) -> gif::Frame<'static> {
+    let (width, height) = (image.width() as u16, image.height() as u16);
+    let mut palette = HashMap::new();
+    let mut pixels = Vec::with_capacity(image.len() / 3);
+    for pixel in image.pixels() {
+        let count = palette.len();
+        let index = *palette.entry(pixel.data).or_insert(count);
+        if index > 255 {
+            return gif::Frame::from_rgb_speed(width, height, image, 10);
+        }
+        pixels.push(index as u8);
+    }
+    let mut colors = vec![0; palette.len() * 3];
+    for (color, index) in palette {
+        colors[index * 3..index * 3 + 3].copy_from_slice(&color);
+    }
+    gif::Frame::from_palette_pixels(width, height, &pixels, &colors, None)
+}
 
-    let _ = image::ImageRgb8(imgbuf).save(fout);
+type Penetration = (Vector<f32>, f32, Vec<Vector<f32>>);
 
-    let mut imgbuf = image::ImageBuffer::from_pixel(imgx, imgy, image::Rgb([25, 25, 25]));
-    let steps = 36000;
-    for i in 0..steps {
-        let i = (i as f32 / steps as f32) * TAU;
-        let dir = Vector::new(i.sin(), i.cos());
+fn render_svg(
+    options: &Options,
+    a: (&ShapeKind, Point<f32>),
+    b: (&ShapeKind, Point<f32>),
+    simplex: &[Vector<f32>],
+    penetration: Option<&Penetration>,
+) -> Result<(), String> {
+    let mut svg = Svg::new(options.size, options.scale);
 
-        let val = shape1.farthest_in_dir(dir);
-        let p = transform(val);
-        imgbuf[(p.x as u32, p.y as u32)] = image::Rgb([255, 0, 0]);
+    svg.group("shapes");
+    for (name, shape, color) in &[("A", a, "rgb(255, 0, 0)"), ("B", b, "rgb(0, 0, 255)")] {
+        let points: Vec<_> = outline(*shape, 0.5 / options.scale)
+            .into_iter()
+            .map(|p| p.coords)
+            .collect();
+        svg.path(&points, true, color, false);
+        svg.label(posed_support(*shape, Vector::new(0., 1.)), name, color);
     }
-    for i in 0..steps {
-        let i = (i as f32 / steps as f32) * TAU;
-        let dir = Vector::new(i.sin(), i.cos());
-
-        let val = shape2.farthest_in_dir(dir);
-        let p = transform(val);
-        imgbuf[(p.x as u32, p.y as u32)] = image::Rgb([0, 0, 255]);
+    if let Some((normal, depth, _)) = penetration {
+        let from = posed_support(a, *normal);
+        let to = from - normal * *depth;
+        svg.path(&[from, to], false, "rgb(0, 255, 128)", false);
+        svg.label(to, &format!("depth {}", depth), "rgb(0, 255, 128)");
     }
-
-    let steps = 10000;
-
-    let val = shape1.farthest_in_dir(penetration);
-    let from = transform(val);
-    let to = transform(val - penetration * depth);
-    for n in 0..steps {
-        let f = n as f32 / steps as f32;
-        let p = from * f + to * (1. - f);
-        imgbuf[(p.x as u32, p.y as u32)] =
-            image::Rgb([(255. * f) as u8, 255, (255. * (1. - f)) as u8]);
+    svg.end_group();
+
+    svg.group("minkowski difference");
+    let outline = minkowski_outline(a, b, 0.5 / options.scale);
+    svg.path(&outline, true, "rgb(255, 128, 128)", false);
+    svg.label(
+        support(a, b, Vector::new(0., 1.)),
+        "A - B",
+        "rgb(255, 128, 128)",
+    );
+    svg.dot(Vector::new(0., 0.), "rgb(0, 255, 0)");
+    svg.label(Vector::new(0., 0.), "origin", "rgb(0, 255, 0)");
+    svg.end_group();
+
+    svg.group("gjk simplex");
+    svg.path(simplex, true, "rgb(255, 255, 0)", true);
+    for (i, &p) in simplex.iter().enumerate() {
+        svg.dot(p, "rgb(255, 255, 0)");
+        svg.label(p, &format!("s{}", i), "rgb(255, 255, 0)");
+    }
+    svg.end_group();
+
+    if let Some((_, _, polytope)) = penetration {
+        svg.group("epa polytope");
+        svg.path(polytope, true, "rgb(0, 255, 255)", false);
+        for (i, &p) in polytope.iter().enumerate() {
+            svg.dot(p, "rgb(0, 255, 255)");
+            svg.label(p, &format!("p{}", i), "rgb(0, 255, 255)");
+        }
+        svg.end_group();
     }
 
-    let p = "visualise_orig.png";
-    let fout = Path::new(&p);
+    svg.save(&options.output)
+}
 
-    let _ = image::ImageRgb8(imgbuf).save(fout);
+fn posed_support((shape, pos): (&ShapeKind, Point<f32>), dir: Vector<f32>) -> Vector<f32> {
+    pos.coords + shape.farthest_in_dir(dir)
 }
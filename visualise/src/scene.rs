@@ -0,0 +1,40 @@
+use std::fs;
+use std::path::Path;
+
+use kolli_desu::shapes::ShapeKind;
+use kolli_desu::Point;
+use serde::Deserialize;
+
+/// Two posed shapes to run GJK and EPA on, loaded from JSON.
+///
+/// ```json
+/// {
+///     "a": { "shape": { "type": "Circle", "center": [0.0, 0.0], "radius": 0.5 } },
+///     "b": { "shape": { "type": "Aabb", "from": [0.0, 0.0], "to": [1.0, 1.0] }, "position": [0.2, 0.0] }
+/// }
+/// ```
+#[derive(Debug, Deserialize)]
+pub struct Scene {
+    pub a: Posed,
+    pub b: Posed,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Posed {
+    pub shape: ShapeKind,
+    #[serde(default)]
+    position: [f32; 2],
+}
+
+impl Posed {
+    pub fn pose(&self) -> (&ShapeKind, Point<f32>) {
+        (&self.shape, Point::new(self.position[0], self.position[1]))
+    }
+}
+
+impl Scene {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let file = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        serde_json::from_str(&file).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
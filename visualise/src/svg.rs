@@ -0,0 +1,108 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use kolli_desu::Vector;
+
+/// SVG document with the same coordinate mapping as the PNG canvas.
+pub struct Svg {
+    size: u32,
+    scale: f32,
+    body: String,
+}
+
+impl Svg {
+    pub fn new(size: u32, scale: f32) -> Self {
+        Svg {
+            size,
+            scale,
+            body: String::new(),
+        }
+    }
+
+    fn transform(&self, v: Vector<f32>) -> Vector<f32> {
+        Vector::new(
+            self.size as f32 / 2. + v.x * self.scale,
+            self.size as f32 / 2. - v.y * self.scale,
+        )
+    }
+
+    /// Starts a group that later elements are added to, so viewers can toggle them together.
+    pub fn group(&mut self, id: &str) {
+        let _ = writeln!(self.body, r#"<g id="{}">"#, escape(id));
+    }
+
+    pub fn end_group(&mut self) {
+        self.body.push_str("</g>\n");
+    }
+
+    /// Adds a path through shape space points, closing it back to the first point if `closed`.
+    pub fn path(&mut self, points: &[Vector<f32>], closed: bool, color: &str, dashed: bool) {
+        if points.is_empty() {
+            return;
+        }
+        let mut data = String::new();
+        for (i, &p) in points.iter().enumerate() {
+            let p = self.transform(p);
+            let command = if i == 0 { 'M' } else { 'L' };
+            let _ = write!(data, "{}{:.2} {:.2} ", command, p.x, p.y);
+        }
+        if closed {
+            data.push('Z');
+        }
+        let dash = if dashed {
+            r#" stroke-dasharray="6 4""#
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            self.body,
+            r#"<path d="{}" fill="none" stroke="{}" stroke-width="1.5"{}/>"#,
+            data.trim_end(),
+            color,
+            dash
+        );
+    }
+
+    pub fn dot(&mut self, p: Vector<f32>, color: &str) {
+        let p = self.transform(p);
+        let _ = writeln!(
+            self.body,
+            r#"<circle cx="{:.2}" cy="{:.2}" r="3" fill="{}"/>"#,
+            p.x, p.y, color
+        );
+    }
+
+    /// Adds a label with its baseline starting a little up and right from `p`.
+    pub fn label(&mut self, p: Vector<f32>, text: &str, color: &str) {
+        let p = self.transform(p);
+        let _ = writeln!(
+            self.body,
+            r#"<text x="{:.2}" y="{:.2}" fill="{}" font-family="monospace" font-size="12">{}</text>"#,
+            p.x + 4.,
+            p.y - 4.,
+            color,
+            escape(text)
+        );
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let document = format!(
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{0}" viewBox="0 0 {0} {0}">"#,
+                "\n",
+                r#"<rect width="100%" height="100%" fill="rgb(25, 25, 25)"/>"#,
+                "\n{1}</svg>\n"
+            ),
+            self.size, self.body
+        );
+        fs::write(path, document).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}